@@ -1,7 +1,8 @@
 fn main() {
     println!("cargo:rustc-link-search=native=/opt/homebrew/opt/openssl@3/lib");
-    println!("cargo:rustc-link-lib=static=ssl");
-    println!("cargo:rustc-link-lib=static=crypto");
+    // 라이브러리 크레이트(rlib)에 묶지 않고 최종 바이너리를 링크할 때 정적으로 링크
+    println!("cargo:rustc-link-lib=static:-bundle=ssl");
+    println!("cargo:rustc-link-lib=static:-bundle=crypto");
     
     // 시스템에 따라 다른 라이브러리 경로 설정
     if cfg!(target_os = "macos") {
@@ -0,0 +1,43 @@
+#ifndef SIGNTOOL_H
+#define SIGNTOOL_H
+
+/*
+ * libsigntool.so 의 C 인터페이스 (src/ffi.rs 와 맞춰 유지)
+ *
+ * 반환값: 검증 결과는 signtool verify --exit-status 의 종료 코드와 같고,
+ * 오류는 signtool 의 오류 종료 코드의 음수이고, 내부 패닉은 SIGNTOOL_ERR_PANIC 이다.
+ * reason 이 NULL 이 아니면 실패 이유나 오류 메시지를 len 바이트 안에서 NUL 로 끝나게 쓴다.
+ */
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    SIGNTOOL_OK = 0,
+    SIGNTOOL_NOT_OK = 2,
+    SIGNTOOL_NOT_SIGNED = 3,
+} signtool_result;
+
+typedef enum {
+    SIGNTOOL_ERR_IO = -1,
+    SIGNTOOL_ERR_ELF = -4,
+    SIGNTOOL_ERR_KEY = -5,
+    SIGNTOOL_ERR_SIGNATURE = -6,
+    SIGNTOOL_ERR_POLICY = -7,
+    SIGNTOOL_ERR_PANIC = -101,
+} signtool_error;
+
+/* pubkey_pem 은 파일 경로가 아니라 PEM 내용 */
+int signtool_verify(const char *path, const char *pubkey_pem, char *reason, size_t len);
+
+/* 기본 정책으로 서명 (output_path 가 NULL 이면 path 에 "-signed" 를 붙인 경로) */
+int signtool_sign(const char *path, const char *privkey_pem, const char *output_path, char *reason, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,142 @@
+use std::ffi::CString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::os::raw::{c_char, c_int, c_void};
+use std::os::unix::fs::{fchown, MetadataExt, OpenOptionsExt, PermissionsExt};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::process;
+
+// 원자적 파일 쓰기
+//
+// 같은 디렉터리의 임시 파일에 모두 쓰고 fsync 한 뒤 rename 으로 대상 파일을 교체한다.
+// 중간에 실패하거나 프로세스가 죽어도 대상 경로에는 이전 파일 또는 완성된 파일만 보인다.
+
+extern "C" {
+    fn flistxattr(fd: c_int, list: *mut c_char, size: usize) -> isize;
+    fn fgetxattr(fd: c_int, name: *const c_char, value: *mut c_void, size: usize) -> isize;
+    fn fsetxattr(fd: c_int, name: *const c_char, value: *const c_void, size: usize, flags: c_int) -> c_int;
+    fn flock(fd: c_int, operation: c_int) -> c_int;
+}
+
+const LOCK_EX: c_int = 2;
+
+// `like` 가 주어지면 그 파일의 권한, 소유자, 확장 속성을 그대로 따른다
+pub fn write_file(target: &Path, data: &[u8], like: Option<&Path>) -> io::Result<()> {
+    write_with(target, like, |out| out.write_all(data))
+}
+
+// 내용을 한 번에 메모리에 두지 않고 write 가 임시 파일에 직접 쓰도록 한다 (버퍼링됨)
+pub fn write_with<T, E, F>(target: &Path, like: Option<&Path>, write: F) -> Result<T, E>
+where
+    E: From<io::Error>,
+    F: FnOnce(&mut dyn Write) -> Result<T, E>,
+{
+    let tmp = temp_path(target);
+    let result = write_temp(&tmp, like, write).and_then(|value| fs::rename(&tmp, target).map(|_| value).map_err(E::from));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+        return result;
+    }
+
+    // rename 자체가 디스크에 남도록 디렉터리도 fsync
+    if let Ok(dir) = File::open(parent_dir(target)) {
+        let _ = dir.sync_all();
+    }
+    result
+}
+
+// target 옆의 "<이름>.lock" 파일에 배타적 flock 을 건다 (돌려받은 File 을 닫으면 풀린다)
+//
+// 대상 파일은 rename 으로 교체되어 inode 가 바뀌므로 대상 파일이 아니라 별도 파일을 잠근다.
+// flock 은 열린 파일마다 걸리므로 다른 프로세스뿐 아니라 같은 프로세스의 다른 스레드와도 배타적이다.
+pub fn lock(target: &Path) -> io::Result<File> {
+    let name = target.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let path = parent_dir(target).join(format!("{}.lock", name));
+    let file = OpenOptions::new().write(true).create(true).truncate(false).mode(0o600).open(path)?;
+    loop {
+        if unsafe { flock(file.as_raw_fd(), LOCK_EX) } == 0 {
+            return Ok(file);
+        }
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+fn temp_path(target: &Path) -> PathBuf {
+    let name = target.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    parent_dir(target).join(format!(".{}.{}.tmp", name, process::id()))
+}
+
+fn write_temp<T, E, F>(tmp: &Path, like: Option<&Path>, write: F) -> Result<T, E>
+where
+    E: From<io::Error>,
+    F: FnOnce(&mut dyn Write) -> Result<T, E>,
+{
+    // 다 쓰기 전까지는 소유자만 읽고 쓸 수 있고 실행 비트도 없다
+    let file = OpenOptions::new().write(true).create_new(true).mode(0o600).open(tmp)?;
+    let mut out = BufWriter::new(file);
+    let value = write(&mut out)?;
+    let file = out.into_inner().map_err(|e| e.into_error())?;
+
+    if let Some(like) = like {
+        let metadata = fs::metadata(like)?;
+        // 다른 사용자의 파일이면 소유자를 바꿀 권한이 없을 수 있다 (그룹만이라도 시도)
+        if fchown(&file, Some(metadata.uid()), Some(metadata.gid())).is_err() {
+            let _ = fchown(&file, None, Some(metadata.gid()));
+        }
+        // chown 은 security.capability 를 지우므로 확장 속성은 소유자를 바꾼 뒤에 복사
+        copy_xattrs(like, &file);
+        // chown 은 setuid 비트도 지우므로 권한은 마지막에 설정
+        file.set_permissions(fs::Permissions::from_mode(metadata.mode() & 0o7777))?;
+    }
+
+    file.sync_all()?;
+    Ok(value)
+}
+
+// 확장 속성 복사 (지원하지 않는 파일 시스템이나 권한 없는 속성은 건너뛴다)
+fn copy_xattrs(from: &Path, to: &File) {
+    let Ok(source) = File::open(from) else {
+        return;
+    };
+    let (src, dst) = (source.as_raw_fd(), to.as_raw_fd());
+
+    unsafe {
+        let size = flistxattr(src, std::ptr::null_mut(), 0);
+        if size <= 0 {
+            return;
+        }
+        let mut names = vec![0u8; size as usize];
+        let size = flistxattr(src, names.as_mut_ptr() as *mut c_char, names.len());
+        if size <= 0 {
+            return;
+        }
+        names.truncate(size as usize);
+
+        for name in names.split(|&b| b == 0).filter(|n| !n.is_empty()) {
+            let Ok(name) = CString::new(name) else {
+                continue;
+            };
+            let len = fgetxattr(src, name.as_ptr(), std::ptr::null_mut(), 0);
+            if len < 0 {
+                continue;
+            }
+            let mut value = vec![0u8; len as usize];
+            let len = fgetxattr(src, name.as_ptr(), value.as_mut_ptr() as *mut c_void, value.len());
+            if len < 0 {
+                continue;
+            }
+            fsetxattr(dst, name.as_ptr(), value.as_ptr() as *const c_void, len as usize, 0);
+        }
+    }
+}
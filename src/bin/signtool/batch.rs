@@ -0,0 +1,79 @@
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use hw4::error;
+use hw4::report::{BatchReport, FileOutcome};
+
+// 디렉터리 트리 아래의 ELF 파일을 여러 스레드로 나누어 처리
+
+// 일반 파일을 모두 찾아 경로 순으로 정렬 (심볼릭 링크는 따라가지 않는다)
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+// 확장자가 아니라 매직 바이트로 ELF 여부를 판단
+// (ELF 매직이 있는데 파싱할 수 없는 파일은 변조되었을 수 있으므로 건너뛰지 않고 처리 실패로 보고한다)
+fn is_elf_file(path: &Path) -> io::Result<bool> {
+    let mut magic = [0u8; 4];
+    match File::open(path)?.read_exact(&mut magic) {
+        Ok(()) => Ok(&magic == b"\x7fELF"),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn run<T, F>(dir: &Path, process: F) -> io::Result<BatchReport<T>>
+where
+    T: Send,
+    F: Fn(&Path) -> error::Result<T> + Sync,
+{
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    files.sort();
+
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(files.len()).max(1);
+    let next = AtomicUsize::new(0);
+    let outcomes: Mutex<Vec<Option<FileOutcome<T>>>> = Mutex::new(files.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(index) else {
+                    break;
+                };
+                let outcome = match is_elf_file(path) {
+                    Ok(false) => FileOutcome::Skipped,
+                    Ok(true) => match process(path) {
+                        Ok(report) => FileOutcome::Done(report),
+                        Err(e) => FileOutcome::Failed(e.to_string()),
+                    },
+                    Err(e) => FileOutcome::Failed(e.to_string()),
+                };
+                outcomes.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(outcome);
+            });
+        }
+    });
+
+    let outcomes = outcomes.into_inner().unwrap_or_else(|e| e.into_inner());
+    Ok(BatchReport {
+        files: files
+            .iter()
+            .zip(outcomes)
+            .map(|(path, outcome)| (path.display().to_string(), outcome.unwrap_or(FileOutcome::Skipped)))
+            .collect(),
+    })
+}
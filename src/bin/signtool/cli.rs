@@ -0,0 +1,201 @@
+// 하위 명령별 옵션을 순서와 관계없이 해석하는 간단한 인자 파서
+//
+// `--long value`, `--long=value`, `-s value` 형식과 값 없는 플래그를 지원하며,
+// `--` 뒤의 인자는 모두 위치 인자로 취급한다.
+
+pub struct OptionSpec {
+    pub long: &'static str,
+    pub short: Option<char>,
+    // 값을 받는 옵션이면 도움말에 표시할 값 이름
+    pub value: Option<&'static str>,
+    pub required: bool,
+    pub help: &'static str,
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub options: &'static [OptionSpec],
+}
+
+#[derive(Default)]
+pub struct Matches {
+    values: Vec<(&'static str, String)>,
+    flags: Vec<&'static str>,
+    pub positionals: Vec<String>,
+}
+
+impl Matches {
+    // 같은 옵션이 여러 번 주어지면 마지막 값을 사용
+    pub fn value(&self, long: &str) -> Option<&str> {
+        self.values.iter().rev().find(|(k, _)| *k == long).map(|(_, v)| v.as_str())
+    }
+
+    pub fn flag(&self, long: &str) -> bool {
+        self.flags.contains(&long)
+    }
+}
+
+pub enum Parsed {
+    Matches(Matches),
+    Help,
+}
+
+impl Command {
+    fn find_long(&self, long: &str) -> Option<&OptionSpec> {
+        self.options.iter().find(|o| o.long == long)
+    }
+
+    fn find_short(&self, short: char) -> Option<&OptionSpec> {
+        self.options.iter().find(|o| o.short == Some(short))
+    }
+
+    pub fn parse(&self, args: &[String]) -> Result<Parsed, String> {
+        let mut matches = Matches::default();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            if arg == "--" {
+                matches.positionals.extend(iter.by_ref().cloned());
+                break;
+            }
+            if arg == "-h" || arg == "--help" {
+                return Ok(Parsed::Help);
+            }
+
+            let (spec, inline) = if let Some(long) = arg.strip_prefix("--") {
+                let (name, inline) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (long, None),
+                };
+                let spec = self.find_long(name).ok_or_else(|| format!("unknown option `--{}`", name))?;
+                (spec, inline)
+            } else if arg.len() == 2 && arg.starts_with('-') && arg != "-" {
+                let short = arg.chars().nth(1).unwrap_or('-');
+                let spec = self.find_short(short).ok_or_else(|| format!("unknown option `{}`", arg))?;
+                (spec, None)
+            } else {
+                matches.positionals.push(arg.clone());
+                continue;
+            };
+
+            match spec.value {
+                Some(_) => {
+                    let value = match inline {
+                        Some(value) => value,
+                        None => iter.next().cloned().ok_or_else(|| format!("option `--{}` requires a value", spec.long))?,
+                    };
+                    matches.values.push((spec.long, value));
+                },
+                None => {
+                    if inline.is_some() {
+                        return Err(format!("option `--{}` does not take a value", spec.long));
+                    }
+                    matches.flags.push(spec.long);
+                },
+            }
+        }
+
+        for spec in self.options.iter().filter(|o| o.required) {
+            if matches.value(spec.long).is_none() {
+                return Err(format!("missing required option `--{}`", spec.long));
+            }
+        }
+        Ok(Parsed::Matches(matches))
+    }
+
+    // 한 줄 사용법: `sign -e <path> -k <path> [options]`
+    pub fn usage(&self, program: &str) -> String {
+        let mut usage = format!("Usage: {} {}", program, self.name);
+        for spec in self.options.iter().filter(|o| o.required) {
+            let flag = match spec.short {
+                Some(short) => format!("-{}", short),
+                None => format!("--{}", spec.long),
+            };
+            usage.push_str(&format!(" {} <{}>", flag, spec.value.unwrap_or("value")));
+        }
+        if self.options.iter().any(|o| !o.required) {
+            usage.push_str(" [options]");
+        }
+        usage
+    }
+
+    pub fn help(&self, program: &str) -> String {
+        let mut help = format!("{}\n\n{}\n\nOptions:\n", self.summary, self.usage(program));
+        for spec in self.options {
+            let short = spec.short.map_or("    ".to_string(), |s| format!("-{}, ", s));
+            let long = match spec.value {
+                Some(value) => format!("--{} <{}>", spec.long, value),
+                None => format!("--{}", spec.long),
+            };
+            help.push_str(&format!("  {}{:<36} {}\n", short, long, spec.help));
+        }
+        help.push_str(&format!("  -h, {:<36} {}\n", "--help", "Print this help"));
+        help
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EXEC, SIGN, VERIFY};
+
+    fn parse(command: &Command, args: &str) -> Result<Matches, String> {
+        let args: Vec<String> = args.split_whitespace().map(str::to_string).collect();
+        match command.parse(&args)? {
+            Parsed::Matches(matches) => Ok(matches),
+            Parsed::Help => Err("help".to_string()),
+        }
+    }
+
+    // 과제 명세의 `sign -e <path> -k <path>` 형식은 그대로 동작해야 한다
+    #[test]
+    fn legacy_sign_arguments() {
+        for args in ["-e prog -k key.pem", "-k key.pem -e prog", "--exe prog --key key.pem", "--exe=prog --key=key.pem"] {
+            let matches = parse(&SIGN, args).unwrap();
+            assert_eq!(matches.value("exe"), Some("prog"), "{}", args);
+            assert_eq!(matches.value("key"), Some("key.pem"), "{}", args);
+            assert!(matches.positionals.is_empty());
+            assert!(!matches.flag("force"));
+        }
+    }
+
+    #[test]
+    fn values_and_flags() {
+        let matches = parse(&SIGN, "-e prog -k a.pem --force -o - --key b.pem --in-place").unwrap();
+        // 마지막 값을 쓴다
+        assert_eq!(matches.value("key"), Some("b.pem"));
+        // `-` 는 옵션이 아니라 값이다
+        assert_eq!(matches.value("output"), Some("-"));
+        assert!(matches.flag("force"));
+        assert!(matches.flag("in-place"));
+        assert_eq!(matches.value("policy"), None);
+    }
+
+    #[test]
+    fn positionals_after_double_dash() {
+        let matches = parse(&EXEC, "-k key.pem -- ./prog -e x --help").unwrap();
+        assert_eq!(matches.value("key"), Some("key.pem"));
+        assert_eq!(matches.positionals, ["./prog", "-e", "x", "--help"]);
+
+        let matches = parse(&VERIFY, "extra -k key.pem").unwrap();
+        assert_eq!(matches.positionals, ["extra"]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse(&SIGN, "-e prog").err().as_deref(), Some("missing required option `--key`"));
+        assert_eq!(parse(&SIGN, "-k key.pem -e").err().as_deref(), Some("option `--exe` requires a value"));
+        assert_eq!(parse(&SIGN, "-k key.pem --bogus").err().as_deref(), Some("unknown option `--bogus`"));
+        assert_eq!(parse(&SIGN, "-k key.pem -z").err().as_deref(), Some("unknown option `-z`"));
+        assert_eq!(parse(&SIGN, "-k key.pem --force=yes").err().as_deref(), Some("option `--force` does not take a value"));
+        assert_eq!(parse(&SIGN, "-e prog --help").err().as_deref(), Some("help"));
+        assert_eq!(parse(&VERIFY, "-h").err().as_deref(), Some("help"));
+    }
+
+    #[test]
+    fn usage_lists_required_options() {
+        assert_eq!(SIGN.usage("signtool"), "Usage: signtool sign -k <path to private_key.pem> [options]");
+        assert!(SIGN.help("signtool").contains("  -e, --exe <path to executable>"));
+    }
+}
@@ -0,0 +1,565 @@
+use std::env;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
+
+mod batch;
+mod cli;
+
+use cli::{Command, Matches, OptionSpec, Parsed};
+use hw4::elf::Elf;
+use hw4::policy::Policy;
+use hw4::report::{BatchItem, BatchReport, DiffReport, FileOutcome, InspectReport, SignReport, VerificationReport, VerificationResult};
+use hw4::error::{self, SigntoolError};
+use hw4::mmap::Mapping;
+use hw4::verify_core::hash::HashAlgorithm;
+use hw4::{atomic, crypto, Options, Signer, Verifier};
+
+// 하위 명령 정의
+const SIGN: Command = Command {
+    name: "sign",
+    summary: "Sign the executable sections of an ELF file into a new .signature section.",
+    options: &[
+        OptionSpec { long: "exe", short: Some('e'), value: Some("path to executable"), required: false, help: "Executable to sign" },
+        OptionSpec { long: "recursive", short: Some('r'), value: Some("dir"), required: false, help: "Sign every ELF file under dir in place" },
+        OptionSpec { long: "key", short: Some('k'), value: Some("path to private_key.pem"), required: true, help: "Private key in PEM format" },
+        OptionSpec { long: "output", short: Some('o'), value: Some("path"), required: false, help: "Output path, `-` for stdout (default: <exe>-signed)" },
+        OptionSpec { long: "in-place", short: None, value: None, required: false, help: "Overwrite the input executable" },
+        OptionSpec { long: "force", short: None, value: None, required: false, help: "Replace an existing signature" },
+        OptionSpec { long: "policy", short: None, value: Some("policy.toml"), required: false, help: "Coverage policy file" },
+        OptionSpec { long: "hash", short: None, value: Some("sha256|sha384|sha512|sha3-256|blake3"), required: false, help: "Hash for the coverage digest (default: sha256)" },
+        OptionSpec { long: "require-hardening", short: None, value: None, required: false, help: "Refuse to sign binaries with hardening gaps" },
+        OptionSpec { long: "version", short: None, value: Some("N"), required: false, help: "Version counter recorded for rollback protection" },
+        OptionSpec { long: "product", short: None, value: Some("id"), required: false, help: "Product id for --version (default: file name)" },
+        OptionSpec { long: "format", short: None, value: Some("text|json"), required: false, help: "Output format (default: text)" },
+    ],
+};
+
+const VERIFY: Command = Command {
+    name: "verify",
+    summary: "Verify the code integrity of a signed ELF file. Prints OK, NOT_OK or NOT_SIGNED.",
+    options: &[
+        OptionSpec { long: "exe", short: Some('e'), value: Some("path to signed executable"), required: false, help: "Signed executable to verify" },
+        OptionSpec { long: "recursive", short: Some('r'), value: Some("dir"), required: false, help: "Verify every ELF file under dir" },
+        OptionSpec { long: "key", short: Some('k'), value: Some("path to public_key.pem"), required: true, help: "Public key in PEM format" },
+        OptionSpec { long: "policy", short: None, value: Some("policy.toml"), required: false, help: "Minimum coverage policy the signature must meet" },
+        OptionSpec { long: "allow-algs", short: None, value: Some("alg,..."), required: false, help: "Accepted signature algorithms" },
+        OptionSpec { long: "min-version-db", short: None, value: Some("state.json"), required: false, help: "Reject versions older than previously seen ones" },
+        OptionSpec { long: "explain", short: None, value: None, required: false, help: "List intact/modified/missing/added sections and header changes" },
+        OptionSpec { long: "cache", short: None, value: Some("cache.db"), required: false, help: "Skip files that previously verified OK and have not changed since" },
+        OptionSpec { long: "no-cache", short: None, value: None, required: false, help: "Ignore --cache and verify every file" },
+        OptionSpec { long: "exit-status", short: None, value: None, required: false, help: "Exit with 0=OK, 2=NOT_OK, 3=NOT_SIGNED, 1=error" },
+        OptionSpec { long: "format", short: None, value: Some("text|json"), required: false, help: "Output format (default: text)" },
+    ],
+};
+
+const INSPECT: Command = Command {
+    name: "inspect",
+    summary: "Decode and print the .signature section of a signed ELF file without verifying it.",
+    options: &[
+        OptionSpec { long: "exe", short: Some('e'), value: Some("path to signed executable"), required: true, help: "Signed executable to inspect" },
+        OptionSpec { long: "format", short: None, value: Some("text|json"), required: false, help: "Output format (default: text)" },
+    ],
+};
+
+const UNSIGN: Command = Command {
+    name: "unsign",
+    summary: "Remove the .signature section and restore the original ELF file byte for byte.",
+    options: &[
+        OptionSpec { long: "exe", short: Some('e'), value: Some("path to signed executable"), required: true, help: "Signed executable to restore" },
+        OptionSpec { long: "output", short: Some('o'), value: Some("path"), required: false, help: "Output path, `-` for stdout (default: <exe>-unsigned)" },
+        OptionSpec { long: "in-place", short: None, value: None, required: false, help: "Overwrite the input executable" },
+    ],
+};
+
+const RESIGN: Command = Command {
+    name: "resign",
+    summary: "Verify a signed ELF file with the old key and replace its signature using the new key.",
+    options: &[
+        OptionSpec { long: "exe", short: Some('e'), value: Some("path to signed executable"), required: true, help: "Signed executable to re-sign" },
+        OptionSpec { long: "old-key", short: None, value: Some("path to public_key.pem"), required: true, help: "Public key of the current signature" },
+        OptionSpec { long: "new-key", short: None, value: Some("path to private_key.pem"), required: true, help: "Private key for the new signature" },
+        OptionSpec { long: "output", short: Some('o'), value: Some("path"), required: false, help: "Output path, `-` for stdout (default: <exe>-resigned)" },
+        OptionSpec { long: "in-place", short: None, value: None, required: false, help: "Overwrite the input executable" },
+        OptionSpec { long: "format", short: None, value: Some("text|json"), required: false, help: "Output format (default: text)" },
+    ],
+};
+
+const DIFF: Command = Command {
+    name: "diff",
+    summary: "Compare two ELF files by section and segment and show which changes the signature covers.",
+    options: &[
+        OptionSpec { long: "old", short: None, value: Some("path"), required: true, help: "Original executable" },
+        OptionSpec { long: "new", short: None, value: Some("path"), required: true, help: "Modified executable" },
+        OptionSpec { long: "policy", short: None, value: Some("policy.toml"), required: false, help: "Coverage policy (default: signed policy of --old)" },
+        OptionSpec { long: "format", short: None, value: Some("text|json"), required: false, help: "Output format (default: text)" },
+    ],
+};
+
+const EXEC: Command = Command {
+    name: "exec",
+    summary: "Verify an ELF file and run exactly the verified contents: exec -k <key> -- <program> [args...]. Refuses anything not OK.",
+    options: &[
+        OptionSpec { long: "key", short: Some('k'), value: Some("path to public_key.pem"), required: true, help: "Public key in PEM format" },
+        OptionSpec { long: "policy", short: None, value: Some("policy.toml"), required: false, help: "Minimum coverage policy the signature must meet" },
+        OptionSpec { long: "allow-algs", short: None, value: Some("alg,..."), required: false, help: "Accepted signature algorithms" },
+        OptionSpec { long: "min-version-db", short: None, value: Some("state.json"), required: false, help: "Reject versions older than previously seen ones" },
+    ],
+};
+
+const COMMANDS: &[Command] = &[SIGN, VERIFY, INSPECT, UNSIGN, RESIGN, DIFF, EXEC];
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let program = args.first().map_or("signtool", String::as_str);
+
+    let Some(command_name) = args.get(1) else {
+        print_usage(program);
+        process::exit(1);
+    };
+
+    match command_name.as_str() {
+        "-h" | "--help" => {
+            print_usage(program);
+            return;
+        },
+        "-V" | "--version" => {
+            println!("signtool {}", env!("CARGO_PKG_VERSION"));
+            return;
+        },
+        _ => {},
+    }
+
+    let Some(command) = COMMANDS.iter().find(|c| c.name == command_name) else {
+        eprintln!("Unknown command: {}", command_name);
+        print_usage(program);
+        process::exit(1);
+    };
+
+    let matches = match command.parse(&args[2..]) {
+        // exec 의 위치 인자는 실행할 프로그램과 그 인자
+        Ok(Parsed::Matches(matches)) if matches.positionals.is_empty() || command.name == "exec" => matches,
+        Ok(Parsed::Matches(matches)) => usage_error(program, command, &format!("unexpected argument `{}`", matches.positionals[0])),
+        Ok(Parsed::Help) => {
+            print!("{}", command.help(program));
+            return;
+        },
+        Err(e) => usage_error(program, command, &e),
+    };
+
+    let json = match matches.value("format") {
+        None | Some("text") => false,
+        Some("json") => true,
+        Some(other) => usage_error(program, command, &format!("unknown format `{}`", other)),
+    };
+
+    // sign / verify 는 -e 와 --recursive 중 하나를 받는다
+    if command.name == "sign" || command.name == "verify" {
+        match (matches.value("exe"), matches.value("recursive")) {
+            (Some(_), Some(_)) => usage_error(program, command, "--exe and --recursive cannot be used together"),
+            (None, None) => usage_error(program, command, "missing required option `--exe` (or `--recursive`)"),
+            _ => {},
+        }
+    }
+
+    match command.name {
+        "sign" if matches.value("recursive").is_some() => {
+            let dir = matches.value("recursive").unwrap_or_default();
+            let private_key_path = matches.value("key").unwrap_or_default();
+            if matches.value("output").is_some() || matches.flag("in-place") {
+                usage_error(program, command, "--output and --in-place cannot be used with --recursive (files are signed in place)");
+            }
+            let options = sign_options(&matches, dir).unwrap_or_else(|e| usage_error(program, command, &e));
+            // 제품 식별자를 주지 않았으면 파일마다 자기 이름을 사용
+            let per_file_product = options.version.is_some() && matches.value("product").is_none();
+            let signer = load_key(Signer::from_file(private_key_path), "signing");
+            
+            let report = batch::run(Path::new(dir), |path| {
+                let path = path.to_string_lossy();
+                let mut options = options.clone();
+                if per_file_product {
+                    options.product_id = Some(file_name(&path).to_string());
+                }
+                sign_executable(&path, &signer, &Output::Path(path.to_string()), &options)
+            });
+            let report = report.unwrap_or_else(|e| {
+                eprintln!("Error signing directory: {}", e);
+                process::exit(1);
+            });
+            print_batch_report(&report, json);
+            if !json {
+                for (path, outcome) in &report.files {
+                    if let FileOutcome::Done(sign_report) = outcome {
+                        for finding in &sign_report.hardening_findings {
+                            eprintln!("Warning: {}: {}", path, finding);
+                        }
+                    }
+                }
+            }
+            if report.count("failed") > 0 {
+                process::exit(1);
+            }
+        },
+        "verify" if matches.value("recursive").is_some() => {
+            let dir = matches.value("recursive").unwrap_or_default();
+            let public_key_path = matches.value("key").unwrap_or_default();
+            let mut verifier = load_verifier(public_key_path, matches.flag("exit-status"));
+            verify_options(&matches, &mut verifier).unwrap_or_else(|e| usage_error(program, command, &e));
+            
+            let report = batch::run(Path::new(dir), |path| verify_executable(&path.to_string_lossy(), &verifier));
+            let report = report.unwrap_or_else(|e| {
+                eprintln!("Error verifying directory: {}", e);
+                process::exit(1);
+            });
+            print_batch_report(&report, json);
+            
+            // 가장 나쁜 결과를 종료 코드로 (동작 오류 > NOT_OK > NOT_SIGNED)
+            if report.count("failed") > 0 {
+                process::exit(1);
+            }
+            if matches.flag("exit-status") {
+                let worst = [VerificationResult::NotOk, VerificationResult::NotSigned]
+                    .into_iter()
+                    .find(|result| report.count(result.as_str()) > 0)
+                    .unwrap_or(VerificationResult::Ok);
+                process::exit(worst.exit_code());
+            }
+        },
+        "sign" => {
+            let executable_path = matches.value("exe").unwrap_or_default();
+            let private_key_path = matches.value("key").unwrap_or_default();
+            let options = sign_options(&matches, executable_path).unwrap_or_else(|e| usage_error(program, command, &e));
+            
+            // 출력 위치 결정 (기본값은 입력 경로 + "-signed")
+            let output = output_option(&matches, executable_path, "-signed").unwrap_or_else(|e| usage_error(program, command, &e));
+            
+            if json && matches!(output, Output::Stdout) {
+                usage_error(program, command, "--format json cannot be combined with --output -");
+            }
+            
+            let signer = load_key(Signer::from_file(private_key_path), "signing");
+            match sign_executable(executable_path, &signer, &output, &options) {
+                Ok(report) => print_sign_report(&report, json),
+                Err(e) => {
+                    eprintln!("Error signing executable: {}", e);
+                    process::exit(e.exit_code());
+                }
+            }
+        },
+        "verify" => {
+            let executable_path = matches.value("exe").unwrap_or_default();
+            let public_key_path = matches.value("key").unwrap_or_default();
+            let exit_status = matches.flag("exit-status");
+            let mut verifier = load_verifier(public_key_path, exit_status);
+            verify_options(&matches, &mut verifier).unwrap_or_else(|e| usage_error(program, command, &e));
+            
+            match verify_executable(executable_path, &verifier) {
+                Ok(report) => {
+                    if json {
+                        println!("{}", report.to_json());
+                    } else {
+                        println!("{}", report.result.as_str());
+                        if verifier.explain {
+                            print!("{}", report.explain_text());
+                        }
+                    }
+                    if exit_status {
+                        process::exit(report.result.exit_code());
+                    }
+                },
+                Err(e) => {
+                    eprintln!("Error verifying executable: {}", e);
+                    process::exit(verify_error_code(&e, exit_status));
+                }
+            }
+        },
+        "inspect" => {
+            let executable_path = matches.value("exe").unwrap_or_default();
+            match inspect_executable(executable_path) {
+                Ok(Some(report)) if json => println!("{}", report.to_json()),
+                Ok(Some(report)) => print!("{}", report.to_text()),
+                Ok(None) if json => println!("{{\"result\":\"NOT_SIGNED\"}}"),
+                Ok(None) => println!("NOT_SIGNED"),
+                Err(e) => {
+                    eprintln!("Error inspecting executable: {}", e);
+                    process::exit(e.exit_code());
+                }
+            }
+        },
+        "unsign" => {
+            let executable_path = matches.value("exe").unwrap_or_default();
+            let output = output_option(&matches, executable_path, "-unsigned").unwrap_or_else(|e| usage_error(program, command, &e));
+            
+            if let Err(e) = unsign_executable(executable_path, &output) {
+                eprintln!("Error unsigning executable: {}", e);
+                process::exit(e.exit_code());
+            }
+        },
+        "resign" => {
+            let executable_path = matches.value("exe").unwrap_or_default();
+            let old_key_path = matches.value("old-key").unwrap_or_default();
+            let new_key_path = matches.value("new-key").unwrap_or_default();
+            let output = output_option(&matches, executable_path, "-resigned").unwrap_or_else(|e| usage_error(program, command, &e));
+            
+            if json && matches!(output, Output::Stdout) {
+                usage_error(program, command, "--format json cannot be combined with --output -");
+            }
+            
+            let old = load_key(Verifier::from_file(old_key_path), "re-signing");
+            let new = load_key(Signer::from_file(new_key_path), "re-signing");
+            match resign_executable(executable_path, &old, &new, &output) {
+                Ok(report) => print_sign_report(&report, json),
+                Err(e) => {
+                    eprintln!("Error re-signing executable: {}", e);
+                    process::exit(e.exit_code());
+                }
+            }
+        },
+        "diff" => {
+            let old_path = matches.value("old").unwrap_or_default();
+            let new_path = matches.value("new").unwrap_or_default();
+            let policy = matches.value("policy").map(|path| load_policy(path).unwrap_or_else(|e| usage_error(program, command, &e)));
+            
+            match diff_executables(old_path, new_path, policy) {
+                Ok(report) if json => println!("{}", report.to_json()),
+                Ok(report) => print!("{}", report.to_text()),
+                Err(e) => {
+                    eprintln!("Error comparing executables: {}", e);
+                    process::exit(e.exit_code());
+                }
+            }
+        },
+        "exec" => {
+            let public_key_path = matches.value("key").unwrap_or_default();
+            let Some(program_path) = matches.positionals.first() else {
+                usage_error(program, command, "missing program to execute after `--`");
+            };
+            let mut verifier = load_key(Verifier::from_file(public_key_path), "verifying");
+            verify_options(&matches, &mut verifier).unwrap_or_else(|e| usage_error(program, command, &e));
+            
+            // 실행에 성공하면 돌아오지 않는다
+            match hw4::exec_verified(Path::new(program_path), &verifier, &matches.positionals) {
+                Ok(report) => {
+                    match &report.reason {
+                        Some(reason) => eprintln!("Refusing to execute {}: {} ({})", program_path, report.result.as_str(), reason),
+                        None => eprintln!("Refusing to execute {}: {}", program_path, report.result.as_str()),
+                    }
+                    process::exit(report.result.exit_code());
+                },
+                Err(e) => {
+                    eprintln!("Error executing {}: {}", program_path, e);
+                    process::exit(e.exit_code());
+                }
+            }
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn print_batch_report<T: BatchItem>(report: &BatchReport<T>, json: bool) {
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.to_text());
+    }
+}
+
+fn print_sign_report(report: &SignReport, json: bool) {
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        for finding in &report.hardening_findings {
+            eprintln!("Warning: {}", finding);
+        }
+    }
+}
+
+fn print_usage(program: &str) {
+    eprintln!("Usage: {} <command> [options]", program);
+    eprintln!("Commands:");
+    for command in COMMANDS {
+        eprintln!("  {:<8} {}", command.name, command.summary);
+    }
+    eprintln!("Run `{} <command> --help` for the options of a command, `{} --version` for the version.", program, program);
+}
+
+fn usage_error(program: &str, command: &Command, message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    eprintln!("{}", command.usage(program));
+    eprintln!("Run `{} {} --help` for more information.", program, command.name);
+    process::exit(1);
+}
+
+// 서명된 실행 파일을 쓸 위치
+enum Output {
+    Path(String),
+    Stdout,
+}
+
+// --output / --in-place 해석 (둘 다 없으면 입력 경로 + suffix)
+fn output_option(matches: &Matches, executable_path: &str, suffix: &str) -> Result<Output, String> {
+    match (matches.value("output"), matches.flag("in-place")) {
+        (Some(_), true) => Err("--output and --in-place cannot be used together".to_string()),
+        (Some("-"), false) => Ok(Output::Stdout),
+        (Some(path), false) => Ok(Output::Path(path.to_string())),
+        (None, true) => Ok(Output::Path(executable_path.to_string())),
+        (None, false) => Ok(Output::Path(format!("{}{}", executable_path, suffix))),
+    }
+}
+
+// 결과 파일을 write 로 쓰고 write 의 결과와 실제로 쓴 위치를 돌려준다
+fn write_output<T, F>(output: &Output, input_path: &str, write: F) -> error::Result<(T, String)>
+where
+    F: FnOnce(&mut dyn Write) -> error::Result<T>,
+{
+    match output {
+        Output::Stdout => {
+            let mut stdout = BufWriter::new(io::stdout().lock());
+            let value = write(&mut stdout)?;
+            stdout.flush()?;
+            Ok((value, "-".to_string()))
+        },
+        Output::Path(output_path) => {
+            // 임시 파일에 쓴 뒤 교체하며, 입력 파일의 권한/소유자/확장 속성을 유지
+            let value = atomic::write_with(Path::new(output_path), Some(Path::new(input_path)), write)?;
+            Ok((value, output_path.clone()))
+        },
+    }
+}
+
+// 키를 읽지 못하면 오류 종류에 맞는 종료 코드로 종료
+fn load_key<T>(key: error::Result<T>, action: &str) -> T {
+    key.unwrap_or_else(|e| {
+        eprintln!("Error {} executable: {}", action, e);
+        process::exit(e.exit_code());
+    })
+}
+
+// verify --exit-status 에서는 결과 코드 2, 3 과 섞이지 않도록 동작 오류를 모두 1 로 끝낸다
+fn verify_error_code(e: &SigntoolError, exit_status: bool) -> i32 {
+    match exit_status {
+        true => 1,
+        false => e.exit_code(),
+    }
+}
+
+fn load_verifier(path: &str, exit_status: bool) -> Verifier {
+    Verifier::from_file(path).unwrap_or_else(|e| {
+        eprintln!("Error verifying executable: {}", e);
+        process::exit(verify_error_code(&e, exit_status));
+    })
+}
+
+fn sign_options(matches: &Matches, executable_path: &str) -> Result<Options, String> {
+    let mut options = Options {
+        require_hardening: matches.flag("require-hardening"),
+        replace: matches.flag("force"),
+        product_id: matches.value("product").map(str::to_string),
+        ..Options::default()
+    };
+    if let Some(path) = matches.value("policy") {
+        options.policy = load_policy(path)?;
+    }
+    if let Some(name) = matches.value("hash") {
+        options.hash = HashAlgorithm::from_name(name).ok_or_else(|| {
+            let names: Vec<&str> = HashAlgorithm::ALL.iter().map(|h| h.name()).collect();
+            format!("invalid --hash: expected one of {}", names.join(", "))
+        })?;
+    }
+    if let Some(version) = matches.value("version") {
+        options.version = Some(version.parse().map_err(|_| "invalid --version: expected a non-negative integer".to_string())?);
+    }
+
+    // 제품 식별자는 버전과 함께만 의미가 있다 (기본값은 실행 파일 이름)
+    if options.version.is_some() && options.product_id.is_none() {
+        options.product_id = Some(file_name(executable_path).to_string());
+    } else if options.version.is_none() && options.product_id.is_some() {
+        return Err("--product requires --version".to_string());
+    }
+    Ok(options)
+}
+
+fn verify_options(matches: &Matches, verifier: &mut Verifier) -> Result<(), String> {
+    verifier.version_db = matches.value("min-version-db").map(str::to_string);
+    verifier.explain = matches.flag("explain");
+    if !matches.flag("no-cache") {
+        verifier.cache = matches.value("cache").map(str::to_string);
+    }
+    if let Some(path) = matches.value("policy") {
+        verifier.policy = Some(load_policy(path)?);
+    }
+    if let Some(value) = matches.value("allow-algs") {
+        verifier.allowed_algorithms = parse_allowed_algorithms(value)?;
+    }
+    Ok(())
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or(path)
+}
+
+fn load_policy(path: &str) -> Result<Policy, String> {
+    Policy::load(path).map_err(|e| format!("failed to load policy {}: {}", path, e))
+}
+
+fn parse_allowed_algorithms(value: &str) -> Result<Vec<String>, String> {
+    let algorithms: Vec<String> = value.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect();
+    for algorithm in &algorithms {
+        crypto::check_allowed_algorithm(algorithm).map_err(|e| format!("invalid --allow-algs: {}", e))?;
+    }
+    Ok(algorithms)
+}
+
+// 실행 파일에 서명하는 함수
+fn sign_executable(input_path: &str, signer: &Signer, output: &Output, options: &Options) -> error::Result<SignReport> {
+    // 입력 파일은 통째로 읽지 않고 매핑
+    let input_data = Mapping::open(input_path)?;
+    
+    let (mut report, output_path) = write_output(output, input_path, |out| hw4::sign_to(&input_data, signer, options, out))?;
+    report.output = output_path;
+    Ok(report)
+}
+
+// 이전 키로 기존 서명을 검증한 뒤 같은 정책과 버전으로 새 키로 다시 서명
+fn resign_executable(input_path: &str, old: &Verifier, new: &Signer, output: &Output) -> error::Result<SignReport> {
+    let input_data = Mapping::open(input_path)?;
+    
+    let (mut report, output_path) = write_output(output, input_path, |out| hw4::resign_to(&input_data, old, new, out))?;
+    report.output = output_path;
+    Ok(report)
+}
+
+// 실행 파일 서명 검증 함수
+fn verify_executable(input_path: &str, verifier: &Verifier) -> error::Result<VerificationReport> {
+    hw4::verify_file(Path::new(input_path), verifier)
+}
+
+// 서명 섹션 내용을 검증 없이 디코딩 (서명되지 않았으면 None)
+fn inspect_executable(input_path: &str) -> error::Result<Option<InspectReport>> {
+    hw4::inspect(&Mapping::open(input_path)?)
+}
+
+// 서명 시 추가된 .signature 섹션을 제거해 원본 파일을 복원
+fn unsign_executable(input_path: &str, output: &Output) -> error::Result<()> {
+    let input_data = Mapping::open(input_path)?;
+    // 서명되지 않았으면 아무것도 쓰지 않은 채 실패하므로 출력 파일도 만들어지지 않는다
+    write_output(output, input_path, |out| match hw4::unsign_to(&input_data, out)? {
+        true => Ok(()),
+        false => Err(SigntoolError::NotSigned),
+    })?;
+    Ok(())
+}
+
+// 두 실행 파일 비교 (정책을 주지 않으면 원본의 서명 정책, 서명되지 않았으면 기본 정책)
+fn diff_executables(old_path: &str, new_path: &str, policy: Option<Policy>) -> error::Result<DiffReport> {
+    let old_data = Mapping::open(old_path)?;
+    let new_data = Mapping::open(new_path)?;
+    if !hw4::is_valid_elf(&old_data) || !hw4::is_valid_elf(&new_data) {
+        return Err(SigntoolError::NotElf);
+    }
+    let old = Elf::parse(&old_data)?;
+    let new = Elf::parse(&new_data)?;
+    
+    let policy = policy.or_else(|| hw4::signed_policy(&old_data)).unwrap_or_default();
+    hw4::diff::diff(&old, &new, &policy)
+}
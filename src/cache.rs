@@ -0,0 +1,95 @@
+use std::fs::{self, Metadata};
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use sha2::{Digest, Sha256};
+
+use crate::atomic;
+use crate::error::Result;
+use crate::json::Value;
+use crate::report::to_hex;
+use crate::Verifier;
+
+// 검증 결과 캐시
+//
+// 파일 식별 정보 (장치, inode, 크기, mtime, ctime) 와 공개 키 지문, 검증 조건을 묶은 키로
+// OK 였던 결과의 서명 알고리즘을 기록한다. 파일을 고치면 ctime 이 바뀌고 ctime 은 사용자가
+// 되돌릴 수 없으므로, 바뀐 파일은 자동으로 다시 검증된다.
+// 캐시 파일을 쓸 수 있으면 검증을 건너뛰게 할 수 있으므로 신뢰하는 사용자만 쓸 수 있어야 한다.
+//
+//   {"<키의 sha256>": "ed25519", ...}
+
+// 이보다 많아지면 오래된 항목부터 지운다
+const MAX_ENTRIES: usize = 4096;
+
+pub struct VerifyCache {
+    path: String,
+    entries: Vec<(String, Value)>,
+}
+
+impl VerifyCache {
+    // 파일이 없거나 형식이 잘못되었으면 빈 캐시로 시작한다 (버려도 다시 검증할 뿐이다)
+    pub fn open(path: &str) -> Result<VerifyCache> {
+        let entries = match fs::read_to_string(path) {
+            Ok(text) => match Value::parse(&text) {
+                Ok(Value::Object(entries)) => entries,
+                _ => Vec::new(),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(VerifyCache { path: path.to_string(), entries })
+    }
+
+    // 이전에 OK 였으면 그때의 서명 알고리즘
+    pub fn lookup(path: &str, key: &str) -> Result<Option<String>> {
+        let cache = VerifyCache::open(path)?;
+        Ok(cache.entries.iter().find(|(k, _)| k == key).and_then(|(_, v)| match v {
+            Value::String(algorithm) => Some(algorithm.clone()),
+            _ => None,
+        }))
+    }
+
+    // 같은 프로세스의 여러 스레드가 기록해도 유실되지 않도록 열기부터 저장까지 직렬화
+    pub fn record(path: &str, key: &str, algorithm: &str) -> Result<()> {
+        static LOCK: Mutex<()> = Mutex::new(());
+        let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut cache = VerifyCache::open(path)?;
+        cache.entries.retain(|(k, _)| k != key);
+        cache.entries.push((key.to_string(), Value::String(algorithm.to_string())));
+        let excess = cache.entries.len().saturating_sub(MAX_ENTRIES);
+        cache.entries.drain(..excess);
+        cache.save()
+    }
+
+    fn save(self) -> Result<()> {
+        let path = Path::new(&self.path);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let text = format!("{}\n", Value::Object(self.entries).to_json());
+        atomic::write_file(path, text.as_bytes(), path.exists().then_some(path))?;
+        Ok(())
+    }
+}
+
+// 파일 식별 정보와 검증 조건으로 만든 캐시 키 (조건이 다르면 다른 항목이 된다)
+pub fn entry_key(metadata: &Metadata, verifier: &Verifier) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"signtool-cache-v1\0");
+    for value in [metadata.dev(), metadata.ino(), metadata.size()] {
+        hasher.update(value.to_le_bytes());
+    }
+    for value in [metadata.mtime(), metadata.mtime_nsec(), metadata.ctime(), metadata.ctime_nsec()] {
+        hasher.update(value.to_le_bytes());
+    }
+    hasher.update(verifier.key.fingerprint());
+    hasher.update(verifier.allowed_algorithms.join(",").as_bytes());
+    hasher.update([0u8]);
+    if let Some(policy) = &verifier.policy {
+        hasher.update(policy.canonical().as_bytes());
+    }
+    to_hex(&hasher.finalize())
+}
@@ -0,0 +1,330 @@
+use std::os::raw::{c_int, c_uchar, c_void};
+use std::ptr;
+use sha2::{Sha256, Digest};
+
+use crate::error::{Result, SigntoolError};
+use crate::verify_core::hash::HashAlgorithm;
+use crate::verify_core::VerifyingKey;
+
+// build.rs 에서 정적으로 링크하는 OpenSSL(libcrypto)을 이용한 서명/검증
+
+// 서명 알고리즘 식별자 (.signature 에 기록된다)
+pub const RSA_PSS_SHA256: &str = "rsa-pss-sha256";
+pub const RSA_PSS_SHA384: &str = "rsa-pss-sha384";
+pub const RSA_PSS_SHA512: &str = "rsa-pss-sha512";
+pub const ED25519: &str = "ed25519";
+
+// 지원하는 알고리즘과 기본 허용 목록
+pub const KNOWN_ALGORITHMS: &[&str] = &[RSA_PSS_SHA256, RSA_PSS_SHA384, RSA_PSS_SHA512, ED25519];
+pub const DEFAULT_ALLOWED_ALGORITHMS: &[&str] = &[RSA_PSS_SHA256, RSA_PSS_SHA384, RSA_PSS_SHA512, ED25519];
+const RSA_PSS_ALGORITHMS: &[&str] = &[RSA_PSS_SHA256, RSA_PSS_SHA384, RSA_PSS_SHA512];
+
+// 허용 목록과 관계없이 항상 거부하는 기준
+pub const MIN_RSA_BITS: u32 = 2048;
+const WEAK_DIGESTS: &[&str] = &["md5", "sha1"];
+
+#[allow(clippy::upper_case_acronyms)]
+#[repr(C)]
+struct BIO {
+    _private: [u8; 0],
+}
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct EVP_PKEY {
+    _private: [u8; 0],
+}
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct EVP_PKEY_CTX {
+    _private: [u8; 0],
+}
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct EVP_MD_CTX {
+    _private: [u8; 0],
+}
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct EVP_MD {
+    _private: [u8; 0],
+}
+
+const EVP_PKEY_RSA: c_int = 6;
+const EVP_PKEY_RSA_PSS: c_int = 912;
+const EVP_PKEY_ED25519: c_int = 1087;
+const RSA_PKCS1_PSS_PADDING: c_int = 6;
+const RSA_PSS_SALTLEN_DIGEST: c_int = -1;
+
+extern "C" {
+    fn BIO_new_mem_buf(buf: *const c_void, len: c_int) -> *mut BIO;
+    fn BIO_free(bio: *mut BIO) -> c_int;
+    fn PEM_read_bio_PrivateKey(bio: *mut BIO, x: *mut *mut EVP_PKEY, cb: *mut c_void, u: *mut c_void) -> *mut EVP_PKEY;
+    fn PEM_read_bio_PUBKEY(bio: *mut BIO, x: *mut *mut EVP_PKEY, cb: *mut c_void, u: *mut c_void) -> *mut EVP_PKEY;
+    fn EVP_PKEY_free(pkey: *mut EVP_PKEY);
+    fn EVP_PKEY_get_base_id(pkey: *const EVP_PKEY) -> c_int;
+    fn EVP_PKEY_get_bits(pkey: *const EVP_PKEY) -> c_int;
+    fn i2d_PUBKEY(pkey: *const EVP_PKEY, out: *mut *mut c_uchar) -> c_int;
+    fn EVP_sha256() -> *const EVP_MD;
+    fn EVP_sha384() -> *const EVP_MD;
+    fn EVP_sha512() -> *const EVP_MD;
+    fn EVP_MD_CTX_new() -> *mut EVP_MD_CTX;
+    fn EVP_MD_CTX_free(ctx: *mut EVP_MD_CTX);
+    fn EVP_DigestSignInit(
+        ctx: *mut EVP_MD_CTX,
+        pctx: *mut *mut EVP_PKEY_CTX,
+        md: *const EVP_MD,
+        engine: *mut c_void,
+        pkey: *mut EVP_PKEY,
+    ) -> c_int;
+    fn EVP_DigestSign(ctx: *mut EVP_MD_CTX, sig: *mut c_uchar, siglen: *mut usize, tbs: *const c_uchar, tbslen: usize) -> c_int;
+    fn EVP_DigestVerifyInit(
+        ctx: *mut EVP_MD_CTX,
+        pctx: *mut *mut EVP_PKEY_CTX,
+        md: *const EVP_MD,
+        engine: *mut c_void,
+        pkey: *mut EVP_PKEY,
+    ) -> c_int;
+    fn EVP_DigestVerify(ctx: *mut EVP_MD_CTX, sig: *const c_uchar, siglen: usize, tbs: *const c_uchar, tbslen: usize) -> c_int;
+    fn EVP_PKEY_CTX_set_rsa_padding(ctx: *mut EVP_PKEY_CTX, pad: c_int) -> c_int;
+    fn EVP_PKEY_CTX_set_rsa_pss_saltlen(ctx: *mut EVP_PKEY_CTX, len: c_int) -> c_int;
+    fn ERR_clear_error();
+}
+
+fn crypto_error(msg: &str) -> SigntoolError {
+    SigntoolError::Crypto { detail: msg.to_string() }
+}
+
+// 허용 목록 검사: 알려진 알고리즘이어야 하고 약한 다이제스트는 거부
+pub fn check_allowed_algorithm(name: &str) -> Result<()> {
+    if WEAK_DIGESTS.iter().any(|d| name.contains(d)) {
+        return Err(SigntoolError::UnsupportedAlgorithm { algorithm: name.to_string(), weak: true });
+    }
+    if !KNOWN_ALGORITHMS.contains(&name) {
+        return Err(SigntoolError::UnsupportedAlgorithm { algorithm: name.to_string(), weak: false });
+    }
+    Ok(())
+}
+
+// EVP_PKEY 소유 래퍼 (EVP_PKEY_free 는 개인 키 성분을 지운 뒤 해제한다)
+struct Pkey(*mut EVP_PKEY);
+
+// 키는 읽은 뒤 바뀌지 않고, OpenSSL 은 같은 EVP_PKEY 로 여러 스레드가 동시에
+// 서명/검증하는 것을 허용하므로 --recursive 의 작업 스레드끼리 공유할 수 있다
+unsafe impl Send for Pkey {}
+unsafe impl Sync for Pkey {}
+
+impl Drop for Pkey {
+    fn drop(&mut self) {
+        unsafe { EVP_PKEY_free(self.0) }
+    }
+}
+
+impl Pkey {
+    fn from_pem(pem: &[u8], private: bool) -> Result<Pkey> {
+        let len = c_int::try_from(pem.len()).map_err(|_| crypto_error("key file too large"))?;
+        unsafe {
+            ERR_clear_error();
+            let bio = BIO_new_mem_buf(pem.as_ptr() as *const c_void, len);
+            if bio.is_null() {
+                return Err(crypto_error("failed to allocate BIO"));
+            }
+            let pkey = if private {
+                PEM_read_bio_PrivateKey(bio, ptr::null_mut(), ptr::null_mut(), ptr::null_mut())
+            } else {
+                PEM_read_bio_PUBKEY(bio, ptr::null_mut(), ptr::null_mut(), ptr::null_mut())
+            };
+            BIO_free(bio);
+            if pkey.is_null() {
+                ERR_clear_error();
+                return Err(SigntoolError::KeyParse { private });
+            }
+            Ok(Pkey(pkey))
+        }
+    }
+
+    // 키 종류에 대응하는 기본 알고리즘 식별자
+    fn algorithm(&self) -> Option<&'static str> {
+        match unsafe { EVP_PKEY_get_base_id(self.0) } {
+            EVP_PKEY_RSA | EVP_PKEY_RSA_PSS => Some(RSA_PSS_SHA256),
+            EVP_PKEY_ED25519 => Some(ED25519),
+            _ => None,
+        }
+    }
+
+    fn bits(&self) -> u32 {
+        unsafe { EVP_PKEY_get_bits(self.0) }.max(0) as u32
+    }
+
+    // 공개 키(SubjectPublicKeyInfo DER)의 SHA-256
+    fn fingerprint(&self) -> Result<[u8; 32]> {
+        unsafe {
+            let len = i2d_PUBKEY(self.0, ptr::null_mut());
+            if len <= 0 {
+                return Err(crypto_error("failed to encode public key"));
+            }
+            let mut der = vec![0u8; len as usize];
+            let mut out = der.as_mut_ptr();
+            if i2d_PUBKEY(self.0, &mut out) != len {
+                return Err(crypto_error("failed to encode public key"));
+            }
+            Ok(Sha256::digest(&der).into())
+        }
+    }
+
+    // 서명/검증 공통 초기화 (RSA 는 알고리즘의 다이제스트와 PSS 패딩, Ed25519 는 자체 해시)
+    fn init(&self, ctx: *mut EVP_MD_CTX, algorithm: &str, sign: bool) -> bool {
+        unsafe {
+            let mut pctx: *mut EVP_PKEY_CTX = ptr::null_mut();
+            let md = match algorithm {
+                ED25519 => ptr::null(),
+                RSA_PSS_SHA384 => EVP_sha384(),
+                RSA_PSS_SHA512 => EVP_sha512(),
+                _ => EVP_sha256(),
+            };
+            let ok = if sign {
+                EVP_DigestSignInit(ctx, &mut pctx, md, ptr::null_mut(), self.0)
+            } else {
+                EVP_DigestVerifyInit(ctx, &mut pctx, md, ptr::null_mut(), self.0)
+            };
+            if ok != 1 {
+                return false;
+            }
+            if RSA_PSS_ALGORITHMS.contains(&algorithm) {
+                return EVP_PKEY_CTX_set_rsa_padding(pctx, RSA_PKCS1_PSS_PADDING) == 1
+                    && EVP_PKEY_CTX_set_rsa_pss_saltlen(pctx, RSA_PSS_SALTLEN_DIGEST) == 1;
+            }
+            true
+        }
+    }
+}
+
+// EVP_MD_CTX 소유 래퍼
+struct MdCtx(*mut EVP_MD_CTX);
+
+impl Drop for MdCtx {
+    fn drop(&mut self) {
+        unsafe { EVP_MD_CTX_free(self.0) }
+    }
+}
+
+impl MdCtx {
+    fn new() -> Result<MdCtx> {
+        let ctx = unsafe { EVP_MD_CTX_new() };
+        if ctx.is_null() {
+            return Err(crypto_error("failed to allocate digest context"));
+        }
+        Ok(MdCtx(ctx))
+    }
+}
+
+// 서명용 개인 키
+pub struct PrivateKey {
+    pkey: Pkey,
+    pub algorithm: &'static str,
+}
+
+impl PrivateKey {
+    pub fn from_pem(pem: &[u8]) -> Result<PrivateKey> {
+        let pkey = Pkey::from_pem(pem, true)?;
+        let algorithm = pkey
+            .algorithm()
+            .ok_or_else(|| SigntoolError::UnsupportedKey { detail: "Unsupported key type".to_string() })?;
+        if algorithm == RSA_PSS_SHA256 && pkey.bits() < MIN_RSA_BITS {
+            return Err(SigntoolError::UnsupportedKey {
+                detail: format!("RSA keys shorter than {} bits are not allowed", MIN_RSA_BITS),
+            });
+        }
+        Ok(PrivateKey { pkey, algorithm })
+    }
+
+    pub fn fingerprint(&self) -> Result<[u8; 32]> {
+        self.pkey.fingerprint()
+    }
+
+    // 서명 범위 다이제스트와 같은 강도의 서명 알고리즘
+    // (RSA 는 sha384 / sha512 이면 같은 다이제스트의 PSS, Ed25519 는 내부적으로 SHA-512 를 쓴다)
+    pub fn algorithm_for(&self, hash: HashAlgorithm) -> &'static str {
+        match (self.algorithm, hash) {
+            (RSA_PSS_SHA256, HashAlgorithm::Sha384) => RSA_PSS_SHA384,
+            (RSA_PSS_SHA256, HashAlgorithm::Sha512) => RSA_PSS_SHA512,
+            (algorithm, _) => algorithm,
+        }
+    }
+
+    pub fn sign(&self, algorithm: &str, data: &[u8]) -> Result<Vec<u8>> {
+        let ctx = MdCtx::new()?;
+        if !self.pkey.init(ctx.0, algorithm, true) {
+            return Err(crypto_error("failed to initialize signing"));
+        }
+        unsafe {
+            let mut len = 0usize;
+            if EVP_DigestSign(ctx.0, ptr::null_mut(), &mut len, data.as_ptr(), data.len()) != 1 {
+                return Err(crypto_error("failed to sign"));
+            }
+            let mut signature = vec![0u8; len];
+            if EVP_DigestSign(ctx.0, signature.as_mut_ptr(), &mut len, data.as_ptr(), data.len()) != 1 {
+                return Err(crypto_error("failed to sign"));
+            }
+            signature.truncate(len);
+            Ok(signature)
+        }
+    }
+}
+
+// 검증용 공개 키
+pub struct PublicKey {
+    pkey: Pkey,
+    // 검증할 때마다 DER 로 인코딩하지 않도록 읽을 때 한 번 계산
+    fingerprint: [u8; 32],
+}
+
+impl PublicKey {
+    pub fn from_pem(pem: &[u8]) -> Result<PublicKey> {
+        let pkey = Pkey::from_pem(pem, false)?;
+        let fingerprint = pkey.fingerprint()?;
+        Ok(PublicKey { pkey, fingerprint })
+    }
+
+    pub fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint
+    }
+
+    // 키가 해당 알고리즘에 쓰일 수 있는지 (키 종류 일치, RSA 최소 길이)
+    pub fn supports(&self, algorithm: &str) -> bool {
+        match self.pkey.algorithm() {
+            Some(RSA_PSS_SHA256) => RSA_PSS_ALGORITHMS.contains(&algorithm) && self.pkey.bits() >= MIN_RSA_BITS,
+            Some(alg) => alg == algorithm,
+            None => false,
+        }
+    }
+
+    pub fn verify(&self, algorithm: &str, data: &[u8], signature: &[u8]) -> bool {
+        let Ok(ctx) = MdCtx::new() else {
+            return false;
+        };
+        if !self.pkey.init(ctx.0, algorithm, false) {
+            return false;
+        }
+        let ok = unsafe { EVP_DigestVerify(ctx.0, signature.as_ptr(), signature.len(), data.as_ptr(), data.len()) };
+        unsafe { ERR_clear_error() };
+        ok == 1
+    }
+}
+
+impl VerifyingKey for PublicKey {
+    fn fingerprint(&self) -> [u8; 32] {
+        self.fingerprint
+    }
+
+    fn supports(&self, algorithm: &str) -> bool {
+        PublicKey::supports(self, algorithm)
+    }
+
+    fn verify(&self, algorithm: &str, data: &[u8], signature: &[u8]) -> bool {
+        PublicKey::verify(self, algorithm, data, signature)
+    }
+}
@@ -0,0 +1,211 @@
+use crate::coverage;
+use crate::elf::{Elf, Section, Symbol, EHDR_SIZE, E_SHNUM_OFFSET, E_SHOFF_OFFSET, PHDR_SIZE, PT_LOAD, SHT_NOBITS};
+use crate::error::Result;
+use crate::explain;
+use crate::policy::Policy;
+use crate::report::{ChangedRange, DiffChange, DiffReport};
+
+// 두 ELF 파일을 섹션과 세그먼트 단위로 맞추어 바뀐 부분을 찾고,
+// 각 변경이 서명 범위(covered)에 들어가 검증에서 드러나는지 표시한다.
+
+pub fn diff(old: &Elf, new: &Elf, policy: &Policy) -> Result<DiffReport> {
+    let covered_old: Vec<usize> = coverage::matching_sections(old, policy).iter().map(|s| s.index).collect();
+    let covered_new: Vec<usize> = coverage::matching_sections(new, policy).iter().map(|s| s.index).collect();
+    let symbols = match new.symbols() {
+        symbols if symbols.is_empty() => old.symbols(),
+        symbols => symbols,
+    };
+
+    let mut changes = Vec::new();
+    let mut ranges = Vec::new();
+
+    // 헤더는 정책이 헤더를 포함할 때만 서명 범위
+    for description in explain::header_changes(&coverage::header_bytes(old)?, &coverage::header_bytes(new)?) {
+        changes.push(DiffChange { description, covered: policy.include_headers });
+    }
+
+    // 이름으로 섹션 맞추기 (같은 이름이 여러 개면 순서대로)
+    let mut used = vec![false; new.sections.len()];
+    for a in old.sections.iter().skip(1) {
+        let covered = covered_old.contains(&a.index);
+        let found = new.sections.iter().skip(1).find(|b| !used[b.index] && b.name == a.name);
+        let Some(b) = found else {
+            changes.push(DiffChange { description: format!("section {} removed", a.name), covered });
+            continue;
+        };
+        used[b.index] = true;
+        let covered = covered || covered_new.contains(&b.index);
+
+        let fields = [
+            ("sh_type", u64::from(a.sh_type), u64::from(b.sh_type)),
+            ("sh_flags", a.sh_flags, b.sh_flags),
+            ("sh_addr", a.sh_addr, b.sh_addr),
+            ("sh_offset", a.sh_offset, b.sh_offset),
+            ("sh_size", a.sh_size, b.sh_size),
+        ];
+        for (field, x, y) in fields {
+            if x != y {
+                changes.push(DiffChange { description: format!("section {}: {} 0x{:x} -> 0x{:x}", a.name, field, x, y), covered });
+            }
+        }
+
+        if a.sh_type == SHT_NOBITS || b.sh_type == SHT_NOBITS {
+            continue;
+        }
+        // 주소와 오프셋은 검사하지 않은 헤더 값이므로 넘치면 주소 없이 보고한다
+        for (start, end) in changed_ranges(old.section_data(a), new.section_data(b)) {
+            let vaddr = if b.sh_addr != 0 { b.sh_addr.checked_add(start as u64) } else { None };
+            ranges.push(ChangedRange {
+                location: format!("section {}", b.name),
+                offset: b.sh_offset.saturating_add(start as u64),
+                size: (end - start) as u64,
+                vaddr,
+                symbol: vaddr.and_then(|addr| symbol_name(&symbols, b, addr)),
+                covered,
+            });
+        }
+    }
+    for b in new.sections.iter().skip(1).filter(|b| !used[b.index]) {
+        changes.push(DiffChange { description: format!("section {} added", b.name), covered: covered_new.contains(&b.index) });
+    }
+
+    // 어느 섹션에도 속하지 않는 로드 세그먼트 영역 (패딩 등)은 정책이 세그먼트 전체를 포함할 때만 서명 범위
+    // (비교한 구간은 마지막 전체 비교에서 제외한다)
+    let mut compared = Vec::new();
+    let loads_old = old.segments.iter().filter(|s| s.p_type == PT_LOAD);
+    let loads_new = new.segments.iter().filter(|s| s.p_type == PT_LOAD);
+    for (index, (a, b)) in loads_old.zip(loads_new).enumerate() {
+        if a.p_offset != b.p_offset {
+            continue;
+        }
+        let covered = coverage::segment_covered(policy, a) || coverage::segment_covered(policy, b);
+        let start = a.p_offset as usize;
+        let end = start.saturating_add(a.p_filesz.min(b.p_filesz) as usize).min(old.data.len()).min(new.data.len());
+        for (gap_start, gap_end) in gaps(old, start, end) {
+            compared.push((gap_start, gap_end));
+            for (x, y) in changed_ranges(&old.data[gap_start..gap_end], &new.data[gap_start..gap_end]) {
+                ranges.push(ChangedRange {
+                    location: format!("LOAD segment {} (outside sections)", index),
+                    offset: (gap_start + x) as u64,
+                    size: (y - x) as u64,
+                    vaddr: b.p_vaddr.checked_add((gap_start + x - start) as u64),
+                    symbol: None,
+                    covered,
+                });
+            }
+        }
+    }
+
+    // 앞에서 비교하지 않은 나머지 바이트를 같은 오프셋끼리 비교
+    // (덧붙은 데이터, p_filesz 뒤의 같은 페이지, p_offset 이 바뀐 세그먼트처럼 어디에도 맞추어지지 않은 변경도 빠뜨리지 않는다)
+    let mut examined = compared;
+    // ELF 헤더는 e_shoff / e_shnum 만 (나머지 필드는 헤더 변경으로 보고한다)
+    examined.extend([(0, E_SHOFF_OFFSET), (E_SHOFF_OFFSET + 8, E_SHNUM_OFFSET), (E_SHNUM_OFFSET + 2, EHDR_SIZE)]);
+    for elf in [old, new] {
+        let phoff = elf.header.e_phoff as usize;
+        examined.push((phoff, phoff.saturating_add(elf.program_header_bytes().len())));
+        examined.extend(section_ranges(elf));
+    }
+    let covered_ranges: Vec<(u64, u64)> = [old, new]
+        .into_iter()
+        .flat_map(|elf| {
+            let file_end = coverage::unsigned_length(elf);
+            coverage::covered_segments(elf, policy).into_iter().filter_map(move |s| coverage::segment_range(s, file_end))
+        })
+        .collect();
+    let len = old.data.len().max(new.data.len());
+    for (start, end) in complement(examined, 0, len) {
+        let old_part = &old.data[start.min(old.data.len())..end.min(old.data.len())];
+        let new_part = &new.data[start.min(new.data.len())..end.min(new.data.len())];
+        for (x, y) in changed_ranges(old_part, new_part) {
+            let (offset, end) = ((start + x) as u64, (start + y) as u64);
+            let location = if offset < EHDR_SIZE as u64 { "ELF header" } else { "outside any section/segment" };
+            ranges.push(ChangedRange {
+                location: location.to_string(),
+                offset,
+                size: end - offset,
+                vaddr: None,
+                symbol: None,
+                covered: covered_ranges.iter().any(|&(s, e)| offset < e && s < end),
+            });
+        }
+    }
+    ranges.sort_by_key(|range| range.offset);
+
+    Ok(DiffReport { changes, ranges })
+}
+
+// 내용이 파일에 있는 섹션들의 [시작, 끝)
+fn section_ranges<'e>(elf: &'e Elf) -> impl Iterator<Item = (usize, usize)> + 'e {
+    elf.sections
+        .iter()
+        .filter(|s| s.index != 0 && s.sh_type != SHT_NOBITS)
+        .map(|s| (s.sh_offset as usize, s.sh_offset.saturating_add(s.sh_size) as usize))
+}
+
+// 서로 다른 바이트가 이어진 구간들 (길이가 다르면 남는 부분도 하나의 구간)
+fn changed_ranges(old: &[u8], new: &[u8]) -> Vec<(usize, usize)> {
+    let common = old.len().min(new.len());
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < common {
+        if old[i] == new[i] {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < common && old[i] != new[i] {
+            i += 1;
+        }
+        ranges.push((start, i));
+    }
+    if old.len() != new.len() {
+        ranges.push((common, old.len().max(new.len())));
+    }
+    ranges
+}
+
+// [start, end) 중 헤더와 섹션이 차지하지 않는 구간
+fn gaps(elf: &Elf, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let phoff = elf.header.e_phoff as usize;
+    let mut used: Vec<(usize, usize)> = vec![(0, EHDR_SIZE), (phoff, phoff.saturating_add(elf.segments.len() * PHDR_SIZE))];
+    used.extend(section_ranges(elf));
+    complement(used, start, end)
+}
+
+// [start, end) 중 used 의 어느 구간에도 들어가지 않는 구간
+fn complement(mut used: Vec<(usize, usize)>, start: usize, end: usize) -> Vec<(usize, usize)> {
+    used.sort();
+
+    let mut gaps = Vec::new();
+    let mut pos = start;
+    for (used_start, used_end) in used {
+        if used_start > pos {
+            gaps.push((pos, used_start.min(end)));
+        }
+        pos = pos.max(used_end);
+        if pos >= end {
+            break;
+        }
+    }
+    if pos < end {
+        gaps.push((pos, end));
+    }
+    gaps.retain(|(s, e)| s < e);
+    gaps
+}
+
+// 주소를 포함하는 심볼, 없으면 같은 섹션 안의 가장 가까운 앞 심볼 (`name+0x10`)
+// (심볼 테이블은 변조되었을 수 있으므로 끝 주소가 넘치는 심볼은 크기가 없는 것으로 본다)
+fn symbol_name(symbols: &[Symbol], section: &Section, addr: u64) -> Option<String> {
+    let candidates = &symbols[..symbols.partition_point(|s| s.value <= addr)];
+    let symbol = candidates
+        .iter()
+        .rev()
+        .find(|s| s.size > 0 && s.value.checked_add(s.size).is_some_and(|end| addr < end))
+        .or_else(|| candidates.iter().rev().find(|s| s.value >= section.sh_addr))?;
+    match addr - symbol.value {
+        0 => Some(symbol.name.clone()),
+        offset => Some(format!("{}+0x{:x}", symbol.name, offset)),
+    }
+}
@@ -0,0 +1,216 @@
+use std::io::{self, Write};
+
+use crate::error::{Result, SigntoolError};
+
+// ELF 파서는 std 없이 쓸 수 있는 verify_core::elf 에 있고, 여기에는 파일을 새로 쓰는 로직만 둔다
+pub use crate::verify_core::elf::*;
+
+// 파일 끝에 새 섹션을 추가한 ELF 이미지를 생성
+pub fn add_section(elf: &Elf, name: &str, contents: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    write_with_section(elf, name, contents, &mut out)?;
+    Ok(out)
+}
+
+// 새 섹션을 추가한 ELF 이미지를 out 에 순서대로 쓴다
+//
+// 원본 바이트는 그대로 두고, 뒤에 섹션 내용, 새 섹션 이름 테이블, 새 섹션 헤더 테이블을
+// 차례로 붙인 뒤 ELF 헤더의 e_shoff / e_shnum 만 고친다. 원본은 고정 크기 버퍼로 나누어
+// 복사하므로 파일 크기와 관계없이 새로 만드는 부분만큼만 메모리를 쓴다.
+pub fn write_with_section<S, W>(elf: &Elf<S>, name: &str, contents: &[u8], out: &mut W) -> Result<()>
+where
+    S: Source + ?Sized,
+    W: Write + ?Sized,
+{
+    write_sections(elf, None, name, contents, out)
+}
+
+// write_with_section 과 같지만 기존 섹션 하나를 섹션 헤더 테이블에서 빼고 쓴다
+//
+// 다른 섹션의 번호와 sh_link / sh_info 가 바뀌지 않도록 항목을 지우지 않고 0 으로 채운 SHT_NULL 로 둔다.
+// 내용은 파일에 그대로 남지만 어느 섹션에도 속하지 않는다.
+pub fn write_replacing_section<S, W>(elf: &Elf<S>, dropped: usize, name: &str, contents: &[u8], out: &mut W) -> Result<()>
+where
+    S: Source + ?Sized,
+    W: Write + ?Sized,
+{
+    if dropped == 0 || dropped >= elf.sections.len() || dropped == elf.header.e_shstrndx as usize {
+        return Err(SigntoolError::MalformedElf { detail: "cannot drop this section".to_string() });
+    }
+    write_sections(elf, Some(dropped), name, contents, out)
+}
+
+fn write_sections<S, W>(elf: &Elf<S>, dropped: Option<usize>, name: &str, contents: &[u8], out: &mut W) -> Result<()>
+where
+    S: Source + ?Sized,
+    W: Write + ?Sized,
+{
+    if elf.sections.is_empty() {
+        return Err(SigntoolError::MalformedElf { detail: "no section headers".to_string() });
+    }
+    let strndx = elf.header.e_shstrndx as usize;
+    let old_strtab = elf.read_section(&elf.sections[strndx])?;
+
+    // 섹션 내용, 기존 이름 테이블 + 새 이름, 8바이트 정렬된 섹션 헤더 테이블 순서
+    let contents_offset = elf.size();
+    let strtab_offset = contents_offset + contents.len() as u64;
+    let name_offset = old_strtab.len() as u32;
+    let strtab_size = old_strtab.len() as u64 + name.len() as u64 + 1;
+    let shoff = (strtab_offset + strtab_size).next_multiple_of(8);
+
+    let mut table = vec![0u8; elf.sections.len() * SHDR_SIZE];
+    read(elf.data, elf.header.e_shoff, &mut table)?;
+
+    // 이름 테이블 섹션 헤더를 새 위치로 갱신
+    let strhdr = strndx * SHDR_SIZE;
+    table[strhdr + 24..strhdr + 32].copy_from_slice(&strtab_offset.to_le_bytes());
+    table[strhdr + 32..strhdr + 40].copy_from_slice(&strtab_size.to_le_bytes());
+    if let Some(index) = dropped {
+        table[index * SHDR_SIZE..(index + 1) * SHDR_SIZE].fill(0);
+    }
+
+    // 새 섹션 헤더
+    let mut shdr = [0u8; SHDR_SIZE];
+    shdr[0..4].copy_from_slice(&name_offset.to_le_bytes());
+    shdr[4..8].copy_from_slice(&SHT_PROGBITS.to_le_bytes());
+    shdr[24..32].copy_from_slice(&contents_offset.to_le_bytes());
+    shdr[32..40].copy_from_slice(&(contents.len() as u64).to_le_bytes());
+    shdr[48..56].copy_from_slice(&1u64.to_le_bytes());
+    table.extend_from_slice(&shdr);
+
+    let header = Header { e_shoff: shoff, e_shnum: elf.header.e_shnum + 1, ..elf.header.clone() };
+    write_data(elf.data, &header, out)?;
+    out.write_all(contents)?;
+    out.write_all(&old_strtab)?;
+    out.write_all(name.as_bytes())?;
+    let padding = (shoff - strtab_offset - strtab_size + 1) as usize;
+    out.write_all(&[0u8; 8][..padding])?;
+    out.write_all(&table)?;
+    Ok(())
+}
+
+// 원본을 한 번에 복사하는 크기
+const COPY_CHUNK: usize = 64 << 10;
+
+// 파일 내용을 쓰되 ELF 헤더의 e_shoff / e_shnum 은 header 의 값으로 바꾼다
+fn write_data<S, W>(data: &S, header: &Header, out: &mut W) -> Result<()>
+where
+    S: Source + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut ehdr = [0u8; EHDR_SIZE];
+    read(data, 0, &mut ehdr)?;
+    ehdr[E_SHOFF_OFFSET..E_SHOFF_OFFSET + 8].copy_from_slice(&header.e_shoff.to_le_bytes());
+    ehdr[E_SHNUM_OFFSET..E_SHNUM_OFFSET + 2].copy_from_slice(&header.e_shnum.to_le_bytes());
+    out.write_all(&ehdr)?;
+    if let Some(bytes) = data.as_bytes() {
+        out.write_all(&bytes[EHDR_SIZE..])?;
+        return Ok(());
+    }
+    let mut buf = vec![0u8; COPY_CHUNK];
+    let mut offset = EHDR_SIZE as u64;
+    while offset < data.size() {
+        let len = (data.size() - offset).min(COPY_CHUNK as u64) as usize;
+        read(data, offset, &mut buf[..len])?;
+        out.write_all(&buf[..len])?;
+        offset += len as u64;
+    }
+    Ok(())
+}
+
+// 실패 원인은 Source 구현이 따로 기록한다 (FileSource::take_error)
+fn read<S: Source + ?Sized>(data: &S, offset: u64, buf: &mut [u8]) -> Result<()> {
+    match data.read_at(offset, buf) {
+        true => Ok(()),
+        false => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "failed to read the input file").into()),
+    }
+}
+
+// add_section 으로 마지막에 추가한 섹션을 제거해 원래 파일을 바이트 단위로 복원
+pub fn remove_last_section(elf: &Elf, name: &str, original_shoff: u64) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    write_elf(&elf.without_last_section(name, original_shoff)?, &mut out)?;
+    Ok(out)
+}
+
+// Elf 가 나타내는 파일 내용을 out 에 쓴다 (without_last_section 의 결과를 파일로 만들 때 사용)
+pub fn write_elf<W: Write + ?Sized>(elf: &Elf, out: &mut W) -> Result<()> {
+    write_data(elf.data, &elf.header, out)
+}
+
+// 심볼 테이블 항목 (이름이 있는 것만)
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    pub name: String,
+    pub value: u64,
+    pub size: u64,
+}
+
+const SYM_SIZE: usize = 24;
+
+// 복원과 diff 에만 쓰이므로 검증 코어가 아닌 이쪽에 둔다
+impl<'a> Elf<'a> {
+    // .symtab 과 .dynsym 의 이름 있는 심볼 (주소 순 정렬)
+    pub fn symbols(&self) -> Vec<Symbol> {
+        let mut symbols = Vec::new();
+        for table in self.sections.iter().filter(|s| matches!(s.sh_type, SHT_SYMTAB | SHT_DYNSYM)) {
+            let Some(strtab) = self.sections.get(table.sh_link as usize) else {
+                continue;
+            };
+            let names = self.section_data(strtab);
+            for entry in self.section_data(table).chunks_exact(SYM_SIZE) {
+                let start = read_u32(entry, 0) as usize;
+                let value = read_u64(entry, 8);
+                if start == 0 || start >= names.len() || value == 0 {
+                    continue;
+                }
+                let end = names[start..].iter().position(|&b| b == 0).map_or(names.len(), |p| start + p);
+                symbols.push(Symbol {
+                    name: String::from_utf8_lossy(&names[start..end]).into_owned(),
+                    value,
+                    size: read_u64(entry, 16),
+                });
+            }
+        }
+        symbols.sort_by_key(|s| s.value);
+        symbols
+    }
+
+    // add_section 으로 마지막에 추가한 섹션이 없던 원래 파일의 Elf
+    //
+    // 추가된 섹션 내용의 시작 위치가 원래 파일의 끝이고, 원래 섹션 헤더 테이블은
+    // 그 앞부분에 그대로 남아 있으므로 앞부분을 e_shoff/e_shnum 만 되돌려 해석하면 된다.
+    // 내용은 복사하지 않고 원래 버퍼의 앞부분을 그대로 가리킨다.
+    pub fn without_last_section(&self, name: &str, original_shoff: u64) -> Result<Elf<'a>> {
+        let last = match self.sections.last() {
+            Some(last) if last.name == name && self.sections.len() >= 2 => last,
+            _ => return Err(cannot_unsign(&format!("{} is not the last section", name))),
+        };
+        let original_len = last.sh_offset as usize;
+        let shnum = self.sections.len() - 1;
+        let old_shoff = original_shoff as usize;
+        let old_end = old_shoff.checked_add(shnum * SHDR_SIZE).filter(|&end| end <= original_len);
+        if original_len > self.data.len() || original_len < EHDR_SIZE || old_end.is_none() {
+            return Err(cannot_unsign("original section header table is out of bounds"));
+        }
+
+        // 이름 테이블을 제외한 기존 섹션 헤더가 그대로인지 확인
+        // (서명할 때 되돌릴 수 없던 이전 .signature 는 0 으로 채워지므로 그런 항목은 건너뛴다)
+        let strndx = self.header.e_shstrndx as usize;
+        let new_shoff = self.header.e_shoff as usize;
+        for index in (0..shnum).filter(|&i| i != strndx) {
+            let old = &self.data[old_shoff + index * SHDR_SIZE..old_shoff + (index + 1) * SHDR_SIZE];
+            let new = &self.data[new_shoff + index * SHDR_SIZE..new_shoff + (index + 1) * SHDR_SIZE];
+            if old != new && new.iter().any(|&b| b != 0) {
+                return Err(cannot_unsign("section headers changed since signing"));
+            }
+        }
+
+        let header = Header { e_shoff: original_shoff, e_shnum: shnum as u16, ..self.header.clone() };
+        Ok(Elf::with_header(&self.data[..original_len], header)?)
+    }
+}
+
+fn cannot_unsign(msg: &str) -> SigntoolError {
+    SigntoolError::CannotUnsign { detail: msg.to_string() }
+}
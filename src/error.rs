@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::verify_core::Error as CoreError;
+
+// signtool 의 오류 종류
+//
+// Display 는 사용자에게 보여줄 메시지이고, exit_code 는 명령줄 도구의 종료 코드다.
+// verify --exit-status 의 0/2/3 과 겹치지 않도록 오류는 1 과 4 이상을 쓴다.
+#[derive(Debug)]
+pub enum SigntoolError {
+    Io(io::Error),
+    NotElf,
+    MalformedElf { detail: String },
+    UnsupportedClass,
+    KeyParse { private: bool },
+    UnsupportedKey { detail: String },
+    UnsupportedAlgorithm { algorithm: String, weak: bool },
+    Crypto { detail: String },
+    SignatureFormat { detail: String },
+    NotSigned,
+    AlreadySigned,
+    LayoutNotRecorded,
+    CannotUnsign { detail: String },
+    OldSignatureInvalid { reason: String },
+    Policy { line: usize, detail: String },
+    MissingSection { name: String },
+    NothingCovered,
+    HardeningRequired { findings: Vec<String> },
+    Json { detail: String },
+    VersionDb { detail: String },
+}
+
+pub type Result<T> = std::result::Result<T, SigntoolError>;
+
+impl SigntoolError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            // 입출력 오류
+            SigntoolError::Io(_) => 1,
+            // 입력 파일이 지원하는 ELF 가 아님
+            SigntoolError::NotElf | SigntoolError::MalformedElf { .. } | SigntoolError::UnsupportedClass => 4,
+            // 키와 알고리즘
+            SigntoolError::KeyParse { .. }
+            | SigntoolError::UnsupportedKey { .. }
+            | SigntoolError::UnsupportedAlgorithm { .. }
+            | SigntoolError::Crypto { .. } => 5,
+            // 서명 섹션의 상태
+            SigntoolError::SignatureFormat { .. }
+            | SigntoolError::NotSigned
+            | SigntoolError::AlreadySigned
+            | SigntoolError::LayoutNotRecorded
+            | SigntoolError::CannotUnsign { .. }
+            | SigntoolError::OldSignatureInvalid { .. } => 6,
+            // 정책, 하드닝 요구, 상태 파일
+            SigntoolError::Policy { .. }
+            | SigntoolError::MissingSection { .. }
+            | SigntoolError::NothingCovered
+            | SigntoolError::HardeningRequired { .. }
+            | SigntoolError::Json { .. }
+            | SigntoolError::VersionDb { .. } => 7,
+        }
+    }
+}
+
+impl fmt::Display for SigntoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigntoolError::Io(e) => write!(f, "{}", e),
+            SigntoolError::NotElf => write!(f, "Invalid ELF file"),
+            SigntoolError::MalformedElf { detail } => write!(f, "Malformed ELF file: {}", detail),
+            SigntoolError::UnsupportedClass => write!(f, "Unsupported ELF class (only little-endian ELF64 is supported)"),
+            SigntoolError::KeyParse { private: true } => write!(f, "Invalid private key"),
+            SigntoolError::KeyParse { private: false } => write!(f, "Invalid public key"),
+            SigntoolError::UnsupportedKey { detail } => write!(f, "{}", detail),
+            SigntoolError::UnsupportedAlgorithm { algorithm, weak: true } => write!(f, "weak algorithm {} cannot be allowed", algorithm),
+            SigntoolError::UnsupportedAlgorithm { algorithm, weak: false } => write!(f, "unknown algorithm {}", algorithm),
+            SigntoolError::Crypto { detail } => write!(f, "{}", detail),
+            SigntoolError::SignatureFormat { detail } => write!(f, "Malformed signature section: {}", detail),
+            SigntoolError::NotSigned => write!(f, "executable is not signed"),
+            SigntoolError::AlreadySigned => write!(f, "executable is already signed (use --force to replace the signature)"),
+            SigntoolError::LayoutNotRecorded => write!(f, "signature does not record the original layout"),
+            SigntoolError::CannotUnsign { detail } => write!(f, "cannot restore the original file: {}", detail),
+            SigntoolError::OldSignatureInvalid { reason } => write!(f, "existing signature does not verify with the old key: {}", reason),
+            SigntoolError::Policy { line, detail } => write!(f, "Invalid policy file: line {}: {}", line, detail),
+            SigntoolError::MissingSection { name } => write!(f, "Required section {} not found", name),
+            SigntoolError::NothingCovered => write!(f, "no section or segment is covered by the policy"),
+            SigntoolError::HardeningRequired { findings } => write!(f, "hardening requirements not met: {}", findings.join("; ")),
+            SigntoolError::Json { detail } => write!(f, "Invalid JSON: {}", detail),
+            SigntoolError::VersionDb { detail } => write!(f, "Invalid version database: {}", detail),
+        }
+    }
+}
+
+impl Error for SigntoolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SigntoolError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SigntoolError {
+    fn from(e: io::Error) -> Self {
+        SigntoolError::Io(e)
+    }
+}
+
+impl From<CoreError> for SigntoolError {
+    fn from(e: CoreError) -> Self {
+        match e {
+            CoreError::NotElf => SigntoolError::NotElf,
+            CoreError::MalformedElf { detail } => SigntoolError::MalformedElf { detail },
+            CoreError::UnsupportedClass => SigntoolError::UnsupportedClass,
+            CoreError::SignatureFormat { detail } => SigntoolError::SignatureFormat { detail },
+            CoreError::Policy { line, detail } => SigntoolError::Policy { line, detail },
+            CoreError::MissingSection { name } => SigntoolError::MissingSection { name },
+            CoreError::NothingCovered => SigntoolError::NothingCovered,
+        }
+    }
+}
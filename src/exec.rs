@@ -0,0 +1,81 @@
+use std::ffi::{CString, OsStr};
+use std::fs::File;
+use std::io;
+use std::os::raw::{c_char, c_int, c_uint};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::path::Path;
+use std::ptr;
+
+// 검증한 내용을 그대로 실행하기 위한 봉인된 메모리 파일
+//
+// 원본 파일을 한 번만 열어 memfd 로 복사하고 쓰기와 크기 변경을 봉인한 뒤,
+// 그 memfd 를 검증하고 같은 memfd 를 fexecve 로 실행한다. 검증 뒤에 경로가 다른 파일로
+// 바뀌거나 누군가 원본 파일에 써도 실행되는 바이트는 검증한 바이트와 같다.
+// (원본 파일의 fd 로 fexecve 하면 같은 inode 에 대한 쓰기가 그대로 반영된다)
+
+const MFD_CLOEXEC: c_uint = 0x1;
+const MFD_ALLOW_SEALING: c_uint = 0x2;
+// 6.3 이상에서 실행 가능한 memfd 임을 밝힌다 (이전 커널은 EINVAL)
+const MFD_EXEC: c_uint = 0x10;
+
+const F_ADD_SEALS: c_int = 1033;
+const F_SEAL_SEAL: c_int = 0x1;
+const F_SEAL_SHRINK: c_int = 0x2;
+const F_SEAL_GROW: c_int = 0x4;
+const F_SEAL_WRITE: c_int = 0x8;
+
+extern "C" {
+    fn memfd_create(name: *const c_char, flags: c_uint) -> c_int;
+    fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+    fn fexecve(fd: c_int, argv: *const *const c_char, envp: *const *const c_char) -> c_int;
+    static environ: *const *const c_char;
+}
+
+pub struct SealedCopy {
+    file: File,
+}
+
+impl SealedCopy {
+    pub fn open(path: &Path) -> io::Result<SealedCopy> {
+        let mut source = File::open(path)?;
+
+        // 이름은 /proc/<pid>/exe 등에 "memfd:<이름>" 으로 보인다
+        let name = path.file_name().map_or(&b"signtool-exec"[..], |n| n.as_bytes());
+        let name = CString::new(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let flags = MFD_CLOEXEC | MFD_ALLOW_SEALING;
+        let mut fd = unsafe { memfd_create(name.as_ptr(), flags | MFD_EXEC) };
+        if fd < 0 && io::Error::last_os_error().kind() == io::ErrorKind::InvalidInput {
+            fd = unsafe { memfd_create(name.as_ptr(), flags) };
+        }
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut file = unsafe { File::from_raw_fd(fd) };
+
+        io::copy(&mut source, &mut file)?;
+        let seals = F_SEAL_SEAL | F_SEAL_SHRINK | F_SEAL_GROW | F_SEAL_WRITE;
+        if unsafe { fcntl(file.as_raw_fd(), F_ADD_SEALS, seals) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(SealedCopy { file })
+    }
+
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    // 현재 환경 변수로 실행 (args[0] 이 argv[0], 성공하면 돌아오지 않는다)
+    pub fn exec<S: AsRef<OsStr>>(&self, args: &[S]) -> io::Error {
+        let args: Vec<CString> = match args.iter().map(|a| CString::new(a.as_ref().as_bytes())).collect() {
+            Ok(args) => args,
+            Err(e) => return io::Error::new(io::ErrorKind::InvalidInput, e),
+        };
+        let mut argv: Vec<*const c_char> = args.iter().map(|a| a.as_ptr()).collect();
+        argv.push(ptr::null());
+
+        // close-on-exec 이므로 실행된 프로그램에 fd 가 남지 않는다 (ELF 는 실행 전에 이미 매핑된다)
+        unsafe { fexecve(self.file.as_raw_fd(), argv.as_ptr(), environ) };
+        io::Error::last_os_error()
+    }
+}
@@ -0,0 +1,180 @@
+use crate::elf::{read_u16, read_u32, read_u64, ProgramHeader, Section, EHDR_SIZE, PHDR_SIZE};
+use crate::policy::Policy;
+use crate::report::SectionReport;
+use crate::signature::SignedAttributes;
+use crate::verify_core::hash::Digest;
+
+// verify --explain: 서명에 기록된 섹션/헤더와 현재 파일을 비교해 변조 위치를 찾는다
+
+pub struct Explanation {
+    pub sections: Vec<SectionReport>,
+    pub changes: Vec<String>,
+}
+
+// (이름, 오프셋, 크기)
+const EHDR_FIELDS: &[(&str, usize, usize)] = &[
+    ("e_ident", 0, 16),
+    ("e_type", 16, 2),
+    ("e_machine", 18, 2),
+    ("e_version", 20, 4),
+    ("e_entry", 24, 8),
+    ("e_phoff", 32, 8),
+    ("e_flags", 48, 4),
+    ("e_ehsize", 52, 2),
+    ("e_phentsize", 54, 2),
+    ("e_phnum", 56, 2),
+    ("e_shentsize", 58, 2),
+    ("e_shstrndx", 62, 2),
+];
+
+const PHDR_FIELDS: &[(&str, usize, usize)] = &[
+    ("p_type", 0, 4),
+    ("p_flags", 4, 4),
+    ("p_offset", 8, 8),
+    ("p_vaddr", 16, 8),
+    ("p_paddr", 24, 8),
+    ("p_filesz", 32, 8),
+    ("p_memsz", 40, 8),
+    ("p_align", 48, 8),
+];
+
+pub fn explain(
+    current: &[(&Section, Digest)],
+    segments: &[(&ProgramHeader, Digest)],
+    attributes: &SignedAttributes,
+    current_headers: &[u8],
+    policy: &Policy,
+) -> Explanation {
+    let mut sections = Vec::new();
+    let mut changes = Vec::new();
+    let mut used = vec![false; current.len()];
+
+    for record in &attributes.sections {
+        // 같은 이름의 섹션이 여러 개면 아직 짝지어지지 않은 첫 번째와 비교
+        let found = current.iter().enumerate().find(|(i, (s, _))| !used[*i] && s.name == record.name);
+        let Some((index, (section, digest))) = found else {
+            changes.push(format!("{}: no longer present in the covered sections", record.name));
+            sections.push(SectionReport {
+                name: record.name.clone(),
+                offset: record.sh_offset,
+                size: record.sh_size,
+                digest: record.digest,
+                status: "missing",
+            });
+            continue;
+        };
+        used[index] = true;
+
+        let before = changes.len();
+        let fields = [
+            ("sh_type", u64::from(record.sh_type), u64::from(section.sh_type)),
+            ("sh_flags", record.sh_flags, section.sh_flags),
+            ("sh_addr", record.sh_addr, section.sh_addr),
+            ("sh_offset", record.sh_offset, section.sh_offset),
+            ("sh_size", record.sh_size, section.sh_size),
+        ];
+        for (field, old, new) in fields {
+            if old != new {
+                changes.push(format!("{}: {} 0x{:x} -> 0x{:x}", record.name, field, old, new));
+            }
+        }
+        if record.digest != *digest {
+            changes.push(format!("{}: contents changed", record.name));
+        }
+        sections.push(SectionReport {
+            name: section.name.clone(),
+            offset: section.sh_offset,
+            size: section.sh_size,
+            digest: *digest,
+            status: if changes.len() == before { "intact" } else { "modified" },
+        });
+    }
+
+    for (index, (section, digest)) in current.iter().enumerate() {
+        if !used[index] {
+            changes.push(format!("{}: added to the covered sections", section.name));
+            sections.push(SectionReport {
+                name: section.name.clone(),
+                offset: section.sh_offset,
+                size: section.sh_size,
+                digest: *digest,
+                status: "added",
+            });
+        }
+    }
+
+    // 세그먼트는 서명 범위에 들어간 순서대로 비교한다
+    for (index, record) in attributes.segments.iter().enumerate() {
+        let label = format!("LOAD segment at offset 0x{:x}", record.p_offset);
+        let Some((segment, digest)) = segments.get(index) else {
+            changes.push(format!("{}: no longer present in the covered segments", label));
+            continue;
+        };
+        let fields = [
+            ("p_flags", u64::from(record.p_flags), u64::from(segment.p_flags)),
+            ("p_offset", record.p_offset, segment.p_offset),
+            ("p_vaddr", record.p_vaddr, segment.p_vaddr),
+            ("p_filesz", record.p_filesz, segment.p_filesz),
+        ];
+        for (field, old, new) in fields {
+            if old != new {
+                changes.push(format!("{}: {} 0x{:x} -> 0x{:x}", label, field, old, new));
+            }
+        }
+        if record.digest != *digest {
+            changes.push(format!("{}: contents changed", label));
+        }
+    }
+    for (segment, _) in segments.iter().skip(attributes.segments.len()) {
+        changes.push(format!("LOAD segment at offset 0x{:x}: added to the covered segments", segment.p_offset));
+    }
+
+    match attributes.headers.as_deref() {
+        Some(recorded_headers) => {
+            let suffix = if policy.include_headers { "" } else { " (headers are not covered by the signed policy)" };
+            for change in header_changes(recorded_headers, current_headers) {
+                changes.push(format!("{}{}", change, suffix));
+            }
+        },
+        None => changes.push("headers were not recorded at signing time".to_string()),
+    }
+
+    Explanation { sections, changes }
+}
+
+fn field(bytes: &[u8], offset: usize, size: usize) -> String {
+    match size {
+        2 => format!("0x{:x}", read_u16(bytes, offset)),
+        4 => format!("0x{:x}", read_u32(bytes, offset)),
+        8 => format!("0x{:x}", read_u64(bytes, offset)),
+        _ => bytes[offset..offset + size].iter().map(|b| format!("{:02x}", b)).collect(),
+    }
+}
+
+fn diff_fields(label: &str, fields: &[(&str, usize, usize)], old: &[u8], new: &[u8], changes: &mut Vec<String>) {
+    for &(name, offset, size) in fields {
+        if old[offset..offset + size] != new[offset..offset + size] {
+            changes.push(format!("{}: {} {} -> {}", label, name, field(old, offset, size), field(new, offset, size)));
+        }
+    }
+}
+
+// ELF 헤더와 프로그램 헤더를 필드 단위로 비교
+pub fn header_changes(old: &[u8], new: &[u8]) -> Vec<String> {
+    let mut changes = Vec::new();
+    if old.len() < EHDR_SIZE || new.len() < EHDR_SIZE {
+        changes.push("ELF header: malformed".to_string());
+        return changes;
+    }
+    diff_fields("ELF header", EHDR_FIELDS, old, new, &mut changes);
+
+    let old_phdrs: Vec<&[u8]> = old[EHDR_SIZE..].chunks_exact(PHDR_SIZE).collect();
+    let new_phdrs: Vec<&[u8]> = new[EHDR_SIZE..].chunks_exact(PHDR_SIZE).collect();
+    if old_phdrs.len() != new_phdrs.len() {
+        changes.push(format!("program headers: count {} -> {}", old_phdrs.len(), new_phdrs.len()));
+    }
+    for (index, (old, new)) in old_phdrs.iter().zip(&new_phdrs).enumerate() {
+        diff_fields(&format!("program header {}", index), PHDR_FIELDS, old, new, &mut changes);
+    }
+    changes
+}
@@ -0,0 +1,182 @@
+use std::ffi::{CStr, OsStr};
+use std::fs::File;
+use std::io;
+use std::os::raw::{c_char, c_int};
+use std::os::unix::ffi::OsStrExt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use crate::crypto::PublicKey;
+use crate::error::{Result, SigntoolError};
+use crate::source::FileSource;
+use crate::{atomic, Options, Signer, Verifier};
+
+// C 에서 호출하는 서명/검증 함수 (선언은 include/signtool.h)
+//
+// 반환값은 검증 결과이면 VerificationResult::exit_code 와 같은 값
+// (OK=0, NOT_OK=2, NOT_SIGNED=3) 이고, 오류이면 SigntoolError::exit_code 의 음수다.
+// 패닉은 C 로 넘기지 않고 ERR_PANIC 으로 바꾼다.
+// reason 이 NULL 이 아니면 실패 이유나 오류 메시지를 len 바이트 안에서 NUL 로 끝나게 쓴다.
+//
+// 모든 문자열 인자는 NUL 로 끝나는 유효한 포인터여야 하고 (output_path 는 NULL 가능),
+// reason 은 NULL 이거나 len 바이트를 쓸 수 있는 버퍼여야 한다.
+
+// 내부에서 패닉이 났을 때의 반환값 (Rust 의 패닉 종료 코드 101 의 음수)
+pub const ERR_PANIC: c_int = -101;
+
+// 서명된 실행 파일 검증 (pubkey_pem 은 파일 경로가 아니라 PEM 내용)
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn signtool_verify(path: *const c_char, pubkey_pem: *const c_char, reason: *mut c_char, len: usize) -> c_int {
+    guard(reason, len, || {
+        let verifier = Verifier::new(PublicKey::from_pem(c_str(pubkey_pem)?.to_bytes())?);
+        // 호출한 프로세스가 SIGBUS 로 죽지 않도록 매핑하지 않고, 헤더와 서명 범위만 pread 로 읽는다
+        let source = FileSource::new(File::open(c_path(path)?)?)?;
+        let report = crate::verify_source(&source, &verifier)?;
+        write_reason(reason, len, report.reason.as_deref().unwrap_or(""));
+        Ok(report.result.exit_code())
+    })
+}
+
+// 기본 정책으로 서명해 output_path (NULL 이면 path 에 "-signed" 를 붙인 경로) 에 쓴다
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn signtool_sign(
+    path: *const c_char,
+    privkey_pem: *const c_char,
+    output_path: *const c_char,
+    reason: *mut c_char,
+    len: usize,
+) -> c_int {
+    guard(reason, len, || {
+        let signer = Signer::from_pem(c_str(privkey_pem)?.to_bytes())?;
+        let input_path = c_path(path)?;
+        let output_path = match output_path.is_null() {
+            true => {
+                let mut signed = input_path.clone().into_os_string();
+                signed.push("-signed");
+                PathBuf::from(signed)
+            },
+            false => c_path(output_path)?,
+        };
+        // 입력은 필요한 부분만 읽고 출력은 나누어 복사하므로 파일이 커도 메모리 사용량은 일정하다
+        let source = FileSource::new(File::open(&input_path)?)?;
+        atomic::write_with(&output_path, Some(&input_path), |out| {
+            crate::sign_file_to(&source, &signer, &Options::default(), out)
+        })?;
+        write_reason(reason, len, "");
+        Ok(0)
+    })
+}
+
+// f 를 실행해 오류는 음수 코드로, 패닉은 ERR_PANIC 으로 바꾼다 (C 프레임으로 풀려 나가면 UB)
+unsafe fn guard(reason: *mut c_char, len: usize, f: impl FnOnce() -> Result<c_int>) -> c_int {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(code)) => code,
+        Ok(Err(e)) => error_code(&e, reason, len),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            write_reason(reason, len, &format!("internal error: {}", message));
+            ERR_PANIC
+        },
+    }
+}
+
+unsafe fn c_str<'a>(s: *const c_char) -> Result<&'a CStr> {
+    if s.is_null() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "NULL argument").into());
+    }
+    Ok(CStr::from_ptr(s))
+}
+
+unsafe fn c_path(s: *const c_char) -> Result<PathBuf> {
+    Ok(Path::new(OsStr::from_bytes(c_str(s)?.to_bytes())).to_path_buf())
+}
+
+unsafe fn error_code(e: &SigntoolError, reason: *mut c_char, len: usize) -> c_int {
+    write_reason(reason, len, &e.to_string());
+    -e.exit_code()
+}
+
+// 버퍼 크기에 맞게 잘라 NUL 로 끝나게 복사
+unsafe fn write_reason(reason: *mut c_char, len: usize, message: &str) {
+    if reason.is_null() || len == 0 {
+        return;
+    }
+    let n = message.len().min(len - 1);
+    ptr::copy_nonoverlapping(message.as_ptr() as *const c_char, reason, n);
+    *reason.add(n) = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process::Command;
+
+    use crate::VerificationResult;
+
+    // include/signtool.h 의 상수와 함수 타입이 이 파일과 맞는지 C 컴파일러로 확인
+    #[test]
+    fn header_matches_rust() {
+        // C 쪽과 같은 타입이어야 아래 C 의 대입이 경고 없이 컴파일된다
+        let _: unsafe extern "C" fn(*const c_char, *const c_char, *mut c_char, usize) -> c_int = signtool_verify;
+        let _: unsafe extern "C" fn(*const c_char, *const c_char, *const c_char, *mut c_char, usize) -> c_int =
+            signtool_sign;
+
+        let io = SigntoolError::Io(io::Error::other("")).exit_code();
+        let elf = SigntoolError::NotElf.exit_code();
+        let key = SigntoolError::KeyParse { private: false }.exit_code();
+        let signature = SigntoolError::NotSigned.exit_code();
+        let policy = SigntoolError::NothingCovered.exit_code();
+        let source = format!(
+            r#"#include "signtool.h"
+_Static_assert(SIGNTOOL_OK == {ok}, "OK");
+_Static_assert(SIGNTOOL_NOT_OK == {not_ok}, "NOT_OK");
+_Static_assert(SIGNTOOL_NOT_SIGNED == {not_signed}, "NOT_SIGNED");
+_Static_assert(SIGNTOOL_ERR_IO == -{io}, "ERR_IO");
+_Static_assert(SIGNTOOL_ERR_ELF == -{elf}, "ERR_ELF");
+_Static_assert(SIGNTOOL_ERR_KEY == -{key}, "ERR_KEY");
+_Static_assert(SIGNTOOL_ERR_SIGNATURE == -{signature}, "ERR_SIGNATURE");
+_Static_assert(SIGNTOOL_ERR_POLICY == -{policy}, "ERR_POLICY");
+_Static_assert(SIGNTOOL_ERR_PANIC == {panic}, "ERR_PANIC");
+int (*verify)(const char *, const char *, char *, size_t) = signtool_verify;
+int (*sign)(const char *, const char *, const char *, char *, size_t) = signtool_sign;
+"#,
+            ok = VerificationResult::Ok.exit_code(),
+            not_ok = VerificationResult::NotOk.exit_code(),
+            not_signed = VerificationResult::NotSigned.exit_code(),
+            panic = ERR_PANIC,
+        );
+
+        let dir = env::temp_dir().join(format!("hw4-header-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("header.c");
+        fs::write(&file, source).unwrap();
+        let output = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+            .args(["-std=c11", "-Wall", "-Werror", "-c", "-o"])
+            .arg(dir.join("header.o"))
+            .arg("-I")
+            .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("include"))
+            .arg(&file)
+            .output()
+            .expect("failed to run the C compiler");
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn panic_becomes_error_code() {
+        let mut reason = [0 as c_char; 64];
+        let code = unsafe { guard(reason.as_mut_ptr(), reason.len(), || panic!("boom")) };
+        assert_eq!(code, ERR_PANIC);
+        let message = unsafe { CStr::from_ptr(reason.as_ptr()) };
+        assert_eq!(message.to_str().unwrap(), "internal error: boom");
+    }
+}
@@ -0,0 +1,50 @@
+use crate::elf::{
+    Elf, DF_1_NOW, DF_BIND_NOW, DT_BIND_NOW, DT_FLAGS, DT_FLAGS_1, ET_EXEC, PF_W, PF_X, PT_DYNAMIC,
+    PT_GNU_RELRO, PT_GNU_STACK, PT_LOAD, Source,
+};
+
+// 서명 전 하드닝 점검
+//
+// 쓰기와 실행이 동시에 가능한 메모리가 있으면 서명된 코드도 실행 중에 바뀔 수 있으므로
+// 코드 무결성이 의미가 없어진다. 발견된 문제를 사람이 읽을 수 있는 문장으로 돌려준다.
+pub fn audit<S: Source + ?Sized>(elf: &Elf<S>) -> Vec<String> {
+    let mut findings = Vec::new();
+
+    // W^X: 쓰기와 실행이 모두 가능한 PT_LOAD
+    for (i, segment) in elf.segments.iter().enumerate() {
+        if segment.p_type == PT_LOAD && segment.p_flags & PF_W != 0 && segment.p_flags & PF_X != 0 {
+            findings.push(format!("W^X violation: PT_LOAD segment {} is both writable and executable", i));
+        }
+    }
+
+    // 실행 가능한 스택
+    match elf.segments.iter().find(|s| s.p_type == PT_GNU_STACK) {
+        Some(stack) if stack.p_flags & PF_X != 0 => findings.push("executable stack (PT_GNU_STACK has PF_X)".to_string()),
+        Some(_) => {},
+        None => findings.push("no PT_GNU_STACK segment (stack may be executable)".to_string()),
+    }
+
+    // RELRO
+    if !elf.segments.iter().any(|s| s.p_type == PT_GNU_RELRO) {
+        findings.push("no PT_GNU_RELRO segment (relocations stay writable)".to_string());
+    }
+
+    // BIND_NOW (동적 링크된 파일만 해당)
+    let dynamic = elf.dynamic_entries();
+    let flag = |tag: i64| dynamic.iter().filter(|(t, _)| *t == tag).fold(0, |acc, (_, v)| acc | v);
+    if elf.segments.iter().any(|s| s.p_type == PT_DYNAMIC) {
+        let bind_now = dynamic.iter().any(|(t, _)| *t == DT_BIND_NOW)
+            || flag(DT_FLAGS) & DF_BIND_NOW != 0
+            || flag(DT_FLAGS_1) & DF_1_NOW != 0;
+        if !bind_now {
+            findings.push("lazy binding (no BIND_NOW), GOT is not fully read-only".to_string());
+        }
+    }
+
+    // PIE (ET_DYN 은 PIE 또는 공유 라이브러리이므로 ET_EXEC 만 문제로 본다)
+    if elf.header.e_type == ET_EXEC {
+        findings.push("not a position-independent executable (PIE)".to_string());
+    }
+
+    findings
+}
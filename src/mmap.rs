@@ -0,0 +1,77 @@
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+use std::os::raw::{c_int, c_long, c_void};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::ptr;
+use std::slice;
+
+// 읽기 전용 파일 메모리 매핑
+//
+// 파일 전체를 힙에 읽어 들이지 않고 필요한 페이지만 커널이 올려 주므로,
+// 수 GB 짜리 실행 파일도 서명 범위와 헤더만큼만 실제로 읽는다.
+// 매핑한 뒤 다른 프로세스가 파일을 잘라내면 접근 시 SIGBUS 가 날 수 있는데,
+// 서명 출력은 임시 파일에 쓴 뒤 rename 하므로 입력 파일을 직접 자르지는 않는다.
+// 다른 프로세스 안에서 불리는 C ABI 와 검증 경로는 SIGBUS 로 호출한 프로세스를 죽이지 않도록
+// 매핑 대신 source::FileSource 로 필요한 부분만 읽는다.
+
+const PROT_READ: c_int = 1;
+const MAP_PRIVATE: c_int = 2;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+extern "C" {
+    fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: c_long) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+pub struct Mapping {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+// 읽기 전용 매핑이므로 여러 스레드에서 동시에 읽어도 된다
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Mapping> {
+        Mapping::from_file(&File::open(path)?)
+    }
+
+    // 이미 연 파일을 매핑 (fstat 한 파일과 매핑한 내용이 같은 파일이어야 할 때)
+    pub fn from_file(file: &File) -> io::Result<Mapping> {
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file too large to map"))?;
+
+        // 길이 0 은 mmap 할 수 없으므로 빈 매핑으로 둔다
+        if len == 0 {
+            return Ok(Mapping { ptr: ptr::null_mut(), len: 0 });
+        }
+        let ptr = unsafe { mmap(ptr::null_mut(), len, PROT_READ, MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if ptr == MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        // 매핑은 파일 디스크립터를 닫아도 유지된다
+        Ok(Mapping { ptr, len })
+    }
+}
+
+impl Deref for Mapping {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            unsafe { munmap(self.ptr, self.len) };
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::cmp::Reverse;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::verify_core::hash::{Digest, DigestMany, Sequential};
+
+// 섹션별 해시를 scoped 스레드로 나누어 계산
+//
+// 결과는 입력 순서대로 돌려주고 이를 합치는 서명 범위 다이제스트는 순차로 누적하므로,
+// 서명과 검증 결과는 한 스레드에서 계산한 것과 같다.
+
+// 이보다 작은 입력은 스레드를 만드는 비용이 더 크므로 한 스레드에서 계산
+const PARALLEL_THRESHOLD: u64 = 1 << 20;
+
+pub struct Threads;
+
+impl DigestMany for Threads {
+    fn digest_many(&self, sizes: &[u64], digest: &(dyn Fn(usize) -> Digest + Sync), out: &mut [Digest]) {
+        let total: u64 = sizes.iter().sum();
+        let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(sizes.len());
+        if workers < 2 || total < PARALLEL_THRESHOLD {
+            return Sequential.digest_many(sizes, digest, out);
+        }
+
+        // 큰 섹션부터 나누어 주어 마지막에 큰 섹션 하나만 남아 기다리지 않도록
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by_key(|&index| Reverse(sizes[index]));
+        let next = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        while let Some(&index) = order.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            done.push((index, digest(index)));
+                        }
+                        done
+                    })
+                })
+                .collect();
+            // 모든 위치는 정확히 한 번씩 나누어 주었으므로 빠진 것이 없다
+            for handle in handles {
+                for (index, value) in handle.join().unwrap_or_else(|e| panic::resume_unwind(e)) {
+                    out[index] = value;
+                }
+            }
+        });
+    }
+}
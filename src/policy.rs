@@ -0,0 +1,13 @@
+use std::fs;
+
+use crate::error::Result;
+
+// 정책 파싱과 비교는 std 없이 쓸 수 있는 verify_core::policy 에 있고, 여기에는 파일 읽기만 둔다
+pub use crate::verify_core::policy::*;
+
+impl Policy {
+    pub fn load(path: &str) -> Result<Policy> {
+        let text = fs::read_to_string(path)?;
+        Ok(Policy::parse(&text)?)
+    }
+}
@@ -0,0 +1,542 @@
+use std::fmt::Write;
+
+use crate::elf::{PF_R, PF_W, PF_X};
+use crate::json::Value;
+use crate::signature::SignedAttributes;
+use crate::verify_core::hash::{Digest, HashAlgorithm};
+
+// sign / verify 결과를 기계가 읽을 수 있는 형태로 모은 보고서
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationResult {
+    Ok,
+    NotOk,
+    NotSigned,
+}
+
+impl VerificationResult {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VerificationResult::Ok => "OK",
+            VerificationResult::NotOk => "NOT_OK",
+            VerificationResult::NotSigned => "NOT_SIGNED",
+        }
+    }
+
+    // verify --exit-status 의 종료 코드 (1 은 동작 오류에 쓰인다)
+    pub fn exit_code(self) -> i32 {
+        match self {
+            VerificationResult::Ok => 0,
+            VerificationResult::NotOk => 2,
+            VerificationResult::NotSigned => 3,
+        }
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(out, "{:02x}", b);
+    }
+    out
+}
+
+// 서명 범위에 포함된 섹션 하나
+pub struct SectionReport {
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+    pub digest: Digest,
+    pub status: &'static str,
+}
+
+impl SectionReport {
+    fn to_value(&self) -> Value {
+        let mut entries = vec![
+            ("name".to_string(), Value::String(self.name.clone())),
+            ("offset".to_string(), Value::Number(self.offset.to_string())),
+            ("size".to_string(), Value::Number(self.size.to_string())),
+            ("digest".to_string(), Value::String(to_hex(&self.digest))),
+        ];
+        if !self.status.is_empty() {
+            entries.push(("status".to_string(), Value::String(self.status.to_string())));
+        }
+        Value::Object(entries)
+    }
+}
+
+fn optional_string(value: Option<String>) -> Value {
+    value.map_or(Value::Null, Value::String)
+}
+
+pub struct VerificationReport {
+    pub result: VerificationResult,
+    pub reason: Option<String>,
+    pub algorithm: Option<String>,
+    pub key_fingerprint: Option<[u8; 32]>,
+    pub sections: Vec<SectionReport>,
+    // verify --explain 으로 찾은 변경 사항
+    pub changes: Option<Vec<String>>,
+    // 검증 캐시에서 가져온 결과 (섹션별 결과는 없다)
+    pub cached: bool,
+}
+
+impl Default for VerificationReport {
+    fn default() -> Self {
+        VerificationReport::new()
+    }
+}
+
+impl VerificationReport {
+    pub fn new() -> VerificationReport {
+        VerificationReport {
+            result: VerificationResult::NotOk,
+            reason: None,
+            algorithm: None,
+            key_fingerprint: None,
+            sections: Vec::new(),
+            changes: None,
+            cached: false,
+        }
+    }
+
+    // 결과와 이유를 기록하고 보고서를 돌려준다
+    pub fn finish(mut self, result: VerificationResult, reason: &str) -> VerificationReport {
+        self.result = result;
+        if !reason.is_empty() {
+            self.reason = Some(reason.to_string());
+        }
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_value().to_json()
+    }
+
+    fn to_value(&self) -> Value {
+        let mut entries = vec![
+            ("result".to_string(), Value::String(self.result.as_str().to_string())),
+            ("reason".to_string(), optional_string(self.reason.clone())),
+            ("algorithm".to_string(), optional_string(self.algorithm.clone())),
+            ("key_fingerprint".to_string(), optional_string(self.key_fingerprint.map(|f| to_hex(&f)))),
+            ("sections".to_string(), Value::Array(self.sections.iter().map(SectionReport::to_value).collect())),
+            ("cached".to_string(), Value::Bool(self.cached)),
+        ];
+        if let Some(changes) = &self.changes {
+            entries.push(("changes".to_string(), Value::Array(changes.iter().cloned().map(Value::String).collect())));
+        }
+        Value::Object(entries)
+    }
+
+    // verify --explain 의 텍스트 출력 (결과 줄 다음에 붙는다)
+    pub fn explain_text(&self) -> String {
+        let mut out = String::new();
+        if let Some(reason) = &self.reason {
+            let _ = writeln!(out, "Reason: {}", reason);
+        }
+        for section in &self.sections {
+            let _ = writeln!(out, "  {:<20} {:<10} offset 0x{:08x}  size 0x{:08x}", section.name, section.status, section.offset, section.size);
+        }
+        for change in self.changes.iter().flatten() {
+            let _ = writeln!(out, "Changed: {}", change);
+        }
+        out
+    }
+}
+
+// sign 으로 서명한 내용
+pub struct SignReport {
+    pub output: String,
+    pub algorithm: String,
+    pub key_fingerprint: [u8; 32],
+    pub policy_hash: [u8; 32],
+    pub hash: HashAlgorithm,
+    pub coverage_digest: Digest,
+    pub version: Option<u64>,
+    pub product_id: Option<String>,
+    pub signing_time: Option<u64>,
+    pub hardening_findings: Vec<String>,
+    pub sections: Vec<SectionReport>,
+}
+
+impl SignReport {
+    pub fn to_json(&self) -> String {
+        self.to_value().to_json()
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Object(vec![
+            ("output".to_string(), Value::String(self.output.clone())),
+            ("algorithm".to_string(), Value::String(self.algorithm.clone())),
+            ("key_fingerprint".to_string(), Value::String(to_hex(&self.key_fingerprint))),
+            ("policy_hash".to_string(), Value::String(to_hex(&self.policy_hash))),
+            ("hash".to_string(), Value::String(self.hash.name().to_string())),
+            ("coverage_digest".to_string(), Value::String(to_hex(&self.coverage_digest))),
+            ("version".to_string(), self.version.map_or(Value::Null, |v| Value::Number(v.to_string()))),
+            ("product_id".to_string(), optional_string(self.product_id.clone())),
+            ("signing_time".to_string(), self.signing_time.map_or(Value::Null, |t| Value::String(format_time(t)))),
+            (
+                "hardening_findings".to_string(),
+                Value::Array(self.hardening_findings.iter().cloned().map(Value::String).collect()),
+            ),
+            ("sections".to_string(), Value::Array(self.sections.iter().map(SectionReport::to_value).collect())),
+        ])
+    }
+}
+
+// 유닉스 시간을 UTC ISO 8601 문자열로 변환
+pub fn format_time(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // 1970-01-01 부터의 일 수를 그레고리력 날짜로 (Howard Hinnant 의 civil_from_days)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+// inspect 로 디코딩한 .signature 내용 (검증하지 않은 값)
+pub struct InspectReport {
+    pub format_version: u16,
+    pub attributes: SignedAttributes,
+    pub signature_size: usize,
+    pub sections: Vec<SectionReport>,
+}
+
+impl InspectReport {
+    pub fn to_text(&self) -> String {
+        let a = &self.attributes;
+        let mut out = String::new();
+        let _ = writeln!(out, "Format version:  {}", self.format_version);
+        let _ = writeln!(out, "Algorithm:       {}", a.algorithm);
+        let _ = writeln!(out, "Key fingerprint: sha256:{}", to_hex(&a.key_fingerprint));
+        let _ = writeln!(out, "Signature:       {} bytes", self.signature_size);
+        let _ = writeln!(out, "Certificates:    none");
+        match a.signing_time {
+            Some(time) => {
+                let _ = writeln!(out, "Signed at:       {} ({})", format_time(time), time);
+            },
+            None => {
+                let _ = writeln!(out, "Signed at:       unknown");
+            },
+        }
+        if let Some(product_id) = &a.product_id {
+            let _ = writeln!(out, "Product:         {}", product_id);
+        }
+        if let Some(version) = a.version {
+            let _ = writeln!(out, "Version:         {}", version);
+        }
+        let _ = writeln!(out, "Policy hash:     {}", to_hex(&a.policy_hash));
+        let _ = writeln!(out, "Policy:");
+        for line in a.policy.lines() {
+            let _ = writeln!(out, "  {}", line);
+        }
+        let _ = writeln!(out, "Hash:            {}", a.hash.name());
+        let _ = writeln!(out, "Coverage digest: {}", to_hex(&a.coverage_digest));
+        if self.sections.iter().any(|section| section.status == "computed") {
+            let _ = writeln!(out, "Covered sections (not recorded; computed from the current file):");
+        } else {
+            let _ = writeln!(out, "Covered sections:");
+        }
+        for section in &self.sections {
+            let _ = writeln!(
+                out,
+                "  {:<20} offset 0x{:08x}  size 0x{:08x}  {} {}",
+                section.name,
+                section.offset,
+                section.size,
+                a.hash.name(),
+                to_hex(&section.digest)
+            );
+        }
+        if !a.segments.is_empty() {
+            let _ = writeln!(out, "Covered segments:");
+            for segment in &a.segments {
+                let _ = writeln!(
+                    out,
+                    "  LOAD {:<15} offset 0x{:08x}  size 0x{:08x}  {} {}",
+                    segment_flags(segment.p_flags),
+                    segment.p_offset,
+                    segment.p_filesz,
+                    a.hash.name(),
+                    to_hex(&segment.digest)
+                );
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let a = &self.attributes;
+        let segments = a
+            .segments
+            .iter()
+            .map(|segment| {
+                Value::Object(vec![
+                    ("flags".to_string(), Value::String(segment_flags(segment.p_flags))),
+                    ("offset".to_string(), Value::Number(segment.p_offset.to_string())),
+                    ("vaddr".to_string(), Value::Number(segment.p_vaddr.to_string())),
+                    ("size".to_string(), Value::Number(segment.p_filesz.to_string())),
+                    ("digest".to_string(), Value::String(to_hex(&segment.digest))),
+                ])
+            })
+            .collect();
+        Value::Object(vec![
+            ("result".to_string(), Value::String("SIGNED".to_string())),
+            ("format_version".to_string(), Value::Number(self.format_version.to_string())),
+            ("algorithm".to_string(), Value::String(a.algorithm.clone())),
+            ("key_fingerprint".to_string(), Value::String(to_hex(&a.key_fingerprint))),
+            ("signature_size".to_string(), Value::Number(self.signature_size.to_string())),
+            ("certificates".to_string(), Value::Array(Vec::new())),
+            ("signing_time".to_string(), a.signing_time.map_or(Value::Null, |t| Value::String(format_time(t)))),
+            ("product_id".to_string(), optional_string(a.product_id.clone())),
+            ("version".to_string(), a.version.map_or(Value::Null, |v| Value::Number(v.to_string()))),
+            ("policy_hash".to_string(), Value::String(to_hex(&a.policy_hash))),
+            ("policy".to_string(), Value::String(a.policy.clone())),
+            ("hash".to_string(), Value::String(a.hash.name().to_string())),
+            ("coverage_digest".to_string(), Value::String(to_hex(&a.coverage_digest))),
+            ("sections".to_string(), Value::Array(self.sections.iter().map(SectionReport::to_value).collect())),
+            ("segments".to_string(), Value::Array(segments)),
+        ])
+        .to_json()
+    }
+}
+
+// 세그먼트 플래그를 readelf 처럼 표시 (예: "R E")
+fn segment_flags(flags: u32) -> String {
+    let flag = |bit: u32, c: char| if flags & bit != 0 { c } else { ' ' };
+    [flag(PF_R, 'R'), flag(PF_W, 'W'), flag(PF_X, 'E')].iter().collect()
+}
+
+// --recursive 처리에서 파일별 결과로 쓰이는 보고서
+pub trait BatchItem {
+    // 합계에 표시할 상태 이름 (이 순서로 출력)
+    const STATUSES: &'static [&'static str];
+
+    fn status(&self) -> &'static str;
+
+    // 상태 옆에 덧붙일 설명
+    fn detail(&self) -> Option<&str> {
+        None
+    }
+
+    fn value(&self) -> Value;
+}
+
+impl BatchItem for SignReport {
+    const STATUSES: &'static [&'static str] = &["signed"];
+
+    fn status(&self) -> &'static str {
+        "signed"
+    }
+
+    fn value(&self) -> Value {
+        self.to_value()
+    }
+}
+
+impl BatchItem for VerificationReport {
+    const STATUSES: &'static [&'static str] = &["OK", "NOT_OK", "NOT_SIGNED"];
+
+    fn status(&self) -> &'static str {
+        self.result.as_str()
+    }
+
+    fn detail(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    fn value(&self) -> Value {
+        self.to_value()
+    }
+}
+
+// 파일 하나의 처리 결과
+pub enum FileOutcome<T> {
+    // ELF 파일이 아니어서 건너뜀
+    Skipped,
+    Failed(String),
+    Done(T),
+}
+
+pub struct BatchReport<T> {
+    pub files: Vec<(String, FileOutcome<T>)>,
+}
+
+impl<T: BatchItem> BatchReport<T> {
+    pub fn count(&self, status: &str) -> usize {
+        self.files
+            .iter()
+            .filter(|(_, outcome)| match outcome {
+                FileOutcome::Skipped => status == "skipped",
+                FileOutcome::Failed(_) => status == "failed",
+                FileOutcome::Done(report) => report.status() == status,
+            })
+            .count()
+    }
+
+    fn totals(&self) -> Vec<(&'static str, usize)> {
+        T::STATUSES.iter().chain(&["failed", "skipped"]).map(|&status| (status, self.count(status))).collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for (path, outcome) in &self.files {
+            let _ = match outcome {
+                FileOutcome::Skipped => writeln!(out, "{}: skipped (not an ELF file)", path),
+                FileOutcome::Failed(e) => writeln!(out, "{}: failed ({})", path, e),
+                FileOutcome::Done(report) => match report.detail() {
+                    Some(detail) => writeln!(out, "{}: {} ({})", path, report.status(), detail),
+                    None => writeln!(out, "{}: {}", path, report.status()),
+                },
+            };
+        }
+        let totals: Vec<String> = self.totals().iter().map(|(status, n)| format!("{} {}", n, status)).collect();
+        let _ = writeln!(out, "{} files: {}", self.files.len(), totals.join(", "));
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let files = self
+            .files
+            .iter()
+            .map(|(path, outcome)| {
+                let mut entries = vec![("path".to_string(), Value::String(path.clone()))];
+                match outcome {
+                    FileOutcome::Skipped => entries.push(("status".to_string(), Value::String("skipped".to_string()))),
+                    FileOutcome::Failed(e) => {
+                        entries.push(("status".to_string(), Value::String("failed".to_string())));
+                        entries.push(("error".to_string(), Value::String(e.clone())));
+                    },
+                    FileOutcome::Done(report) => {
+                        entries.push(("status".to_string(), Value::String(report.status().to_string())));
+                        entries.push(("report".to_string(), report.value()));
+                    },
+                }
+                Value::Object(entries)
+            })
+            .collect();
+        let totals = self
+            .totals()
+            .into_iter()
+            .map(|(status, n)| (status.to_string(), Value::Number(n.to_string())))
+            .collect();
+        Value::Object(vec![
+            ("files".to_string(), Value::Array(files)),
+            ("totals".to_string(), Value::Object(totals)),
+        ])
+        .to_json()
+    }
+}
+
+// diff 결과: 헤더/섹션 구조의 변경과 바뀐 바이트 구간
+pub struct DiffChange {
+    pub description: String,
+    // 서명 범위에 포함되어 검증에서 드러나는지
+    pub covered: bool,
+}
+
+pub struct ChangedRange {
+    pub location: String,
+    // 새 파일 기준 파일 오프셋
+    pub offset: u64,
+    pub size: u64,
+    pub vaddr: Option<u64>,
+    pub symbol: Option<String>,
+    pub covered: bool,
+}
+
+pub struct DiffReport {
+    pub changes: Vec<DiffChange>,
+    pub ranges: Vec<ChangedRange>,
+}
+
+fn coverage_label(covered: bool) -> &'static str {
+    if covered {
+        "[covered]"
+    } else {
+        "[NOT covered]"
+    }
+}
+
+impl DiffReport {
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        if self.changes.is_empty() && self.ranges.is_empty() {
+            let _ = writeln!(out, "No differences");
+            return out;
+        }
+        if !self.changes.is_empty() {
+            let _ = writeln!(out, "Structure changes:");
+            for change in &self.changes {
+                let _ = writeln!(out, "  {:<14} {}", coverage_label(change.covered), change.description);
+            }
+        }
+        if !self.ranges.is_empty() {
+            let _ = writeln!(out, "Changed byte ranges:");
+            for range in &self.ranges {
+                let _ = write!(out, "  {:<14} {}  offset 0x{:08x}  size 0x{:x}", coverage_label(range.covered), range.location, range.offset, range.size);
+                if let Some(vaddr) = range.vaddr {
+                    let _ = write!(out, "  vaddr 0x{:x}", vaddr);
+                }
+                if let Some(symbol) = &range.symbol {
+                    let _ = write!(out, "  <{}>", symbol);
+                }
+                out.push('\n');
+            }
+        }
+        let uncovered = self.changes.iter().filter(|c| !c.covered).count() + self.ranges.iter().filter(|r| !r.covered).count();
+        let total = self.changes.len() + self.ranges.len();
+        let _ = writeln!(out, "{} changes, {} covered, {} not covered", total, total - uncovered, uncovered);
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let changes = self
+            .changes
+            .iter()
+            .map(|c| {
+                Value::Object(vec![
+                    ("description".to_string(), Value::String(c.description.clone())),
+                    ("covered".to_string(), Value::Bool(c.covered)),
+                ])
+            })
+            .collect();
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| {
+                Value::Object(vec![
+                    ("location".to_string(), Value::String(r.location.clone())),
+                    ("offset".to_string(), Value::Number(r.offset.to_string())),
+                    ("size".to_string(), Value::Number(r.size.to_string())),
+                    ("vaddr".to_string(), r.vaddr.map_or(Value::Null, |v| Value::Number(v.to_string()))),
+                    ("symbol".to_string(), optional_string(r.symbol.clone())),
+                    ("covered".to_string(), Value::Bool(r.covered)),
+                ])
+            })
+            .collect();
+        Value::Object(vec![
+            ("identical".to_string(), Value::Bool(self.changes.is_empty() && self.ranges.is_empty())),
+            ("changes".to_string(), Value::Array(changes)),
+            ("ranges".to_string(), Value::Array(ranges)),
+        ])
+        .to_json()
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::atomic;
+use crate::error::{Result, SigntoolError};
+use crate::json::Value;
+
+// 제품별로 지금까지 본 가장 높은 버전을 기록하는 상태 파일
+//
+//   {"licensechk": 3, "signtool": 12}
+pub struct VersionDb {
+    path: String,
+    state: Value,
+}
+
+impl VersionDb {
+    // 파일이 없으면 빈 상태로 시작한다
+    pub fn open(path: &str) -> Result<VersionDb> {
+        let state = match fs::read_to_string(path) {
+            Ok(text) => Value::parse(&text)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Value::Object(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let Value::Object(entries) = &state else {
+            return Err(SigntoolError::VersionDb { detail: "expected an object".to_string() });
+        };
+        if entries.iter().any(|(_, v)| v.as_u64().is_none()) {
+            return Err(SigntoolError::VersionDb { detail: "versions must be integers".to_string() });
+        }
+        Ok(VersionDb { path: path.to_string(), state })
+    }
+
+    pub fn highest(&self, product: &str) -> Option<u64> {
+        self.state.get(product).and_then(Value::as_u64)
+    }
+
+    // 이전에 본 버전보다 낮으면 false, 높으면 기록을 갱신한다
+    pub fn check_and_update(&mut self, product: &str, version: u64) -> Result<bool> {
+        match self.highest(product) {
+            Some(highest) if version < highest => return Ok(false),
+            Some(highest) if version == highest => return Ok(true),
+            _ => {},
+        }
+        self.state.set(product, Value::Number(version.to_string()));
+        self.save()?;
+        Ok(true)
+    }
+
+    // 여러 프로세스와 스레드가 같은 상태 파일을 갱신해도 낮은 버전으로 덮어쓰지 않도록
+    // 열기부터 비교, 저장까지 잠금 파일로 직렬화
+    pub fn check_and_record(path: &str, product: &str, version: u64) -> Result<bool> {
+        let _lock = atomic::lock(Path::new(path))?;
+        VersionDb::open(path)?.check_and_update(product, version)
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Path::new(&self.path);
+        let text = format!("{}\n", self.state.to_json());
+        atomic::write_file(path, text.as_bytes(), path.exists().then_some(path))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::thread;
+
+    // 테스트마다 따로 쓰는 임시 디렉터리
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = env::temp_dir().join(format!("hw4-rollback-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rejects_lower_versions() {
+        let dir = temp_dir("lower");
+        let path = dir.join("versions.json");
+        let path = path.to_str().unwrap();
+
+        assert!(VersionDb::check_and_record(path, "licensechk", 3).unwrap());
+        assert!(VersionDb::check_and_record(path, "licensechk", 3).unwrap());
+        assert!(!VersionDb::check_and_record(path, "licensechk", 2).unwrap());
+        assert!(VersionDb::check_and_record(path, "licensechk", 5).unwrap());
+        assert!(!VersionDb::check_and_record(path, "licensechk", 4).unwrap());
+        // 제품마다 따로 기록한다
+        assert!(VersionDb::check_and_record(path, "signtool", 1).unwrap());
+
+        let db = VersionDb::open(path).unwrap();
+        assert_eq!(db.highest("licensechk"), Some(5));
+        assert_eq!(db.highest("signtool"), Some(1));
+        assert_eq!(db.highest("other"), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn concurrent_records_keep_the_highest() {
+        let dir = temp_dir("concurrent");
+        let path = dir.join("versions.json").to_str().unwrap().to_string();
+
+        let threads: Vec<_> = (1..=16u64)
+            .rev()
+            .map(|version| {
+                let path = path.clone();
+                thread::spawn(move || VersionDb::check_and_record(&path, "product", version).unwrap())
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(VersionDb::open(&path).unwrap().highest("product"), Some(16));
+        assert!(!VersionDb::check_and_record(&path, "product", 15).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_state() {
+        let dir = temp_dir("invalid");
+        let path = dir.join("versions.json");
+        for text in ["[1, 2]", "{\"product\": \"3\"}", "{\"product\": -1}"] {
+            fs::write(&path, text).unwrap();
+            assert!(matches!(
+                VersionDb::check_and_record(path.to_str().unwrap(), "product", 1),
+                Err(SigntoolError::VersionDb { .. })
+            ));
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
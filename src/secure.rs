@@ -0,0 +1,82 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem;
+use std::ops::Deref;
+use std::ptr;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+// 비밀 데이터를 담는 버퍼: drop 시 내용을 0 으로 덮어쓴다
+//
+// Vec 의 재할당은 이전 메모리를 지우지 않으므로, 용량이 모자라면 직접 새 버퍼로 옮기고 이전 것을 지운다.
+pub struct SecureBuffer {
+    data: Vec<u8>,
+}
+
+impl SecureBuffer {
+    pub fn read_file(path: &str) -> io::Result<SecureBuffer> {
+        let mut file = File::open(path)?;
+        // 파이프나 FIFO 는 크기가 0 으로 보이므로 일반 파일일 때만 크기를 시작 용량으로 쓴다
+        let metadata = file.metadata()?;
+        let capacity = if metadata.is_file() { metadata.len() as usize + 1 } else { 4096 };
+        let mut buffer = SecureBuffer { data: Vec::with_capacity(capacity) };
+
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = match file.read(&mut chunk) {
+                Ok(n) => n,
+                Err(e) => {
+                    wipe(&mut chunk);
+                    return Err(e);
+                },
+            };
+            if n == 0 {
+                break;
+            }
+            if buffer.data.len() + n > buffer.data.capacity() {
+                buffer.grow(buffer.data.len() + n);
+            }
+            buffer.data.extend_from_slice(&chunk[..n]);
+        }
+        wipe(&mut chunk);
+        Ok(buffer)
+    }
+
+    // 최소 needed 바이트를 담을 새 버퍼로 옮긴다 (이전 버퍼는 drop 되면서 지워진다)
+    fn grow(&mut self, needed: usize) {
+        let mut data = Vec::with_capacity(needed.max(self.data.capacity() * 2));
+        data.extend_from_slice(&self.data);
+        drop(SecureBuffer { data: mem::replace(&mut self.data, data) });
+    }
+}
+
+impl Deref for SecureBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Drop for SecureBuffer {
+    fn drop(&mut self) {
+        // 길이가 아닌 용량 전체를 지운다
+        let capacity = self.data.capacity();
+        self.data.resize(capacity, 0);
+        wipe(&mut self.data);
+    }
+}
+
+impl fmt::Debug for SecureBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecureBuffer({} bytes)", self.data.len())
+    }
+}
+
+// 최적화로 제거되지 않도록 volatile 쓰기로 0 을 채운다
+fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
@@ -0,0 +1,57 @@
+use std::fs::File;
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::sync::Mutex;
+
+use crate::error::{Result, SigntoolError};
+use crate::verify_core::image::Source;
+
+// pread 로 필요한 부분만 읽는 파일
+//
+// 헤더와 테이블, 서명 범위만 고정 크기 버퍼로 읽으므로 파일 크기와 관계없이 메모리 사용량이 일정하다.
+// 매핑하지 않으므로 읽는 중에 다른 프로세스가 파일을 잘라도 SIGBUS 대신 읽기 오류가 난다
+// (C ABI 처럼 호출한 프로세스를 죽이면 안 되는 곳에서 쓴다).
+pub struct FileSource {
+    file: File,
+    size: u64,
+    // 처음 실패한 읽기의 원인 (Source::read_at 은 성공 여부만 돌려준다)
+    error: Mutex<Option<io::Error>>,
+}
+
+impl FileSource {
+    pub fn new(file: File) -> io::Result<FileSource> {
+        let size = file.metadata()?.len();
+        Ok(FileSource { file, size, error: Mutex::new(None) })
+    }
+
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    // 읽기에 실패했으면 그 오류를 돌려준다
+    pub fn take_error(&self) -> Result<()> {
+        match self.error.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            Some(e) => Err(SigntoolError::Io(e)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Source for FileSource {
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> bool {
+        if offset.checked_add(buf.len() as u64).is_none_or(|end| end > self.size) {
+            return false;
+        }
+        match self.file.read_exact_at(buf, offset) {
+            Ok(()) => true,
+            Err(e) => {
+                self.error.lock().unwrap_or_else(|e| e.into_inner()).get_or_insert(e);
+                false
+            },
+        }
+    }
+}
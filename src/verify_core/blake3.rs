@@ -0,0 +1,253 @@
+// BLAKE3 (해시 모드, 32 바이트 출력)
+//
+// 입력을 1024 바이트 청크로 나누어 각 청크의 체이닝 값을 구하고, 이를 이진 트리로
+// 합친다. 트리는 지금까지 완성된 청크 수의 비트 패턴에 맞춰 스택으로 유지한다.
+
+const OUT_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+
+const IV: [u32; 8] = [0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19];
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+fn round(state: &mut [u32; 16], m: &[u32; 16]) {
+    // 열
+    g(state, 0, 4, 8, 12, m[0], m[1]);
+    g(state, 1, 5, 9, 13, m[2], m[3]);
+    g(state, 2, 6, 10, 14, m[4], m[5]);
+    g(state, 3, 7, 11, 15, m[6], m[7]);
+    // 대각선
+    g(state, 0, 5, 10, 15, m[8], m[9]);
+    g(state, 1, 6, 11, 12, m[10], m[11]);
+    g(state, 2, 7, 8, 13, m[12], m[13]);
+    g(state, 3, 4, 9, 14, m[14], m[15]);
+}
+
+fn compress(chaining_value: &[u32; 8], block: &[u32; 16], counter: u64, block_len: u32, flags: u32) -> [u32; 16] {
+    let mut state = [
+        chaining_value[0], chaining_value[1], chaining_value[2], chaining_value[3],
+        chaining_value[4], chaining_value[5], chaining_value[6], chaining_value[7],
+        IV[0], IV[1], IV[2], IV[3],
+        counter as u32, (counter >> 32) as u32, block_len, flags,
+    ];
+    let mut m = *block;
+    for i in 0..7 {
+        round(&mut state, &m);
+        if i < 6 {
+            m = core::array::from_fn(|j| m[MSG_PERMUTATION[j]]);
+        }
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= chaining_value[i];
+    }
+    state
+}
+
+fn first_8(words: [u32; 16]) -> [u32; 8] {
+    core::array::from_fn(|i| words[i])
+}
+
+fn block_words(block: &[u8; BLOCK_LEN]) -> [u32; 16] {
+    core::array::from_fn(|i| u32::from_le_bytes([block[4 * i], block[4 * i + 1], block[4 * i + 2], block[4 * i + 3]]))
+}
+
+// 아직 ROOT 플래그를 정하지 않은 마지막 압축 입력 (루트이면 ROOT 를 붙여 출력으로 쓴다)
+struct Output {
+    chaining_value: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        first_8(compress(&self.chaining_value, &self.block, self.counter, self.block_len, self.flags))
+    }
+
+    fn root_hash(&self) -> [u8; OUT_LEN] {
+        let words = compress(&self.chaining_value, &self.block, 0, self.block_len, self.flags | ROOT);
+        let mut out = [0u8; OUT_LEN];
+        for (chunk, word) in out.chunks_exact_mut(4).zip(words.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+#[derive(Clone)]
+struct ChunkState {
+    chaining_value: [u32; 8],
+    chunk_counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: u8,
+}
+
+impl ChunkState {
+    fn new(chunk_counter: u64) -> ChunkState {
+        ChunkState { chaining_value: IV, chunk_counter, block: [0; BLOCK_LEN], block_len: 0, blocks_compressed: 0 }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed as usize + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 { CHUNK_START } else { 0 }
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // 마지막 블록은 CHUNK_END 와 함께 압축해야 하므로 더 들어올 때만 압축
+            if self.block_len == BLOCK_LEN {
+                let words = block_words(&self.block);
+                let flags = self.start_flag();
+                self.chaining_value = first_8(compress(&self.chaining_value, &words, self.chunk_counter, BLOCK_LEN as u32, flags));
+                self.blocks_compressed += 1;
+                self.block = [0; BLOCK_LEN];
+                self.block_len = 0;
+            }
+            let n = (BLOCK_LEN - self.block_len).min(input.len());
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&input[..n]);
+            self.block_len += n;
+            input = &input[n..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            chaining_value: self.chaining_value,
+            block: block_words(&self.block),
+            counter: self.chunk_counter,
+            block_len: self.block_len as u32,
+            flags: self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+fn parent_output(left: [u32; 8], right: [u32; 8]) -> Output {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(&left);
+    block[8..].copy_from_slice(&right);
+    Output { chaining_value: IV, block, counter: 0, block_len: BLOCK_LEN as u32, flags: PARENT }
+}
+
+#[derive(Clone)]
+pub struct Blake3 {
+    chunk_state: ChunkState,
+    // 완성된 부분 트리의 체이닝 값 (최대 2^54 청크)
+    cv_stack: [[u32; 8]; 54],
+    cv_stack_len: usize,
+}
+
+impl Default for Blake3 {
+    fn default() -> Self {
+        Blake3::new()
+    }
+}
+
+impl Blake3 {
+    pub fn new() -> Blake3 {
+        Blake3 { chunk_state: ChunkState::new(0), cv_stack: [[0; 8]; 54], cv_stack_len: 0 }
+    }
+
+    // 새로 완성된 청크를 넣으면서, 전체 청크 수의 끝자리 0 비트만큼 부분 트리를 합친다
+    fn add_chunk_chaining_value(&mut self, mut new_cv: [u32; 8], mut total_chunks: u64) {
+        while total_chunks & 1 == 0 {
+            self.cv_stack_len -= 1;
+            new_cv = parent_output(self.cv_stack[self.cv_stack_len], new_cv).chaining_value();
+            total_chunks >>= 1;
+        }
+        self.cv_stack[self.cv_stack_len] = new_cv;
+        self.cv_stack_len += 1;
+    }
+
+    pub fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // 청크가 가득 찼고 입력이 더 있으면 그 청크는 루트가 아니다
+            if self.chunk_state.len() == CHUNK_LEN {
+                let chunk_cv = self.chunk_state.output().chaining_value();
+                let total_chunks = self.chunk_state.chunk_counter + 1;
+                self.add_chunk_chaining_value(chunk_cv, total_chunks);
+                self.chunk_state = ChunkState::new(total_chunks);
+            }
+            let n = (CHUNK_LEN - self.chunk_state.len()).min(input.len());
+            self.chunk_state.update(&input[..n]);
+            input = &input[n..];
+        }
+    }
+
+    pub fn finalize(&self) -> [u8; OUT_LEN] {
+        let mut output = self.chunk_state.output();
+        for i in (0..self.cv_stack_len).rev() {
+            output = parent_output(self.cv_stack[i], output.chaining_value());
+        }
+        output.root_hash()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // BLAKE3 공식 테스트 벡터 (입력은 i % 251 바이트 열, 청크 1024 바이트 경계 전후와 여러 단계의 트리)
+    const VECTORS: &[(usize, &str)] = &[
+        (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"),
+        (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"),
+        (1023, "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11"),
+        (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7"),
+        (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444"),
+        (2049, "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030"),
+        (8193, "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b"),
+        (102400, "bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085"),
+    ];
+
+    fn input(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn known_answers() {
+        for (len, expected) in VECTORS {
+            let mut hasher = Blake3::new();
+            hasher.update(&input(*len));
+            assert_eq!(hex(&hasher.finalize()), *expected, "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn incremental_updates() {
+        for (len, expected) in VECTORS {
+            let data = input(*len);
+            for piece in [1, 7, 64, 1000, 1024] {
+                let mut hasher = Blake3::new();
+                for chunk in data.chunks(piece) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hex(&hasher.finalize()), *expected, "{} bytes in {}-byte pieces", len, piece);
+            }
+        }
+    }
+}
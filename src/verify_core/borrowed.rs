@@ -0,0 +1,798 @@
+use core::str;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use super::hash::{Digest, DigestMany, Hasher, HashAlgorithm, Sequential};
+use super::image::{
+    self, Image, ImageError, ProgramHeader, SectionHeader, Source, EHDR_SIZE, E_SHNUM_OFFSET, E_SHOFF_OFFSET, PF_R, PF_W,
+    PF_X, PT_LOAD, SHT_REL, SHT_RELA, SHT_RELR,
+};
+use super::key::{ct_eq, VerifyingKey};
+
+// 할당 없이 빌린 바이트만으로 하는 검증
+//
+// .signature 와 정책 텍스트는 복사하지 않고 그 자리에서 읽고, 섹션과 세그먼트 다이제스트는
+// 계산하는 대로 서명 범위 다이제스트에 누적하므로 필요한 메모리는 고정 크기 버퍼뿐이다.
+// alloc 쪽의 check 와 sign 도 서명 범위 계산은 여기 것을 쓰므로 두 경로의 결과는 항상 같다.
+
+// 서명 섹션 이름
+pub const SIGNATURE_SECTION_NAME: &str = ".signature";
+
+// .signature 섹션의 바이너리 형식
+//
+//   magic "SGTL" (4) | 형식 버전 u16 | 예약 u16
+//   서명 대상 블록 길이 u32 | 서명 대상 블록 (TLV 레코드)
+//   서명 길이 u32 | 서명
+//
+// TLV 레코드는 태그 u16, 길이 u32, 값으로 이루어지며 모든 정수는 리틀 엔디언이다.
+
+pub const MAGIC: &[u8; 4] = b"SGTL";
+pub const FORMAT_VERSION: u16 = 1;
+
+pub(super) const TAG_ALGORITHM: u16 = 1;
+pub(super) const TAG_POLICY_HASH: u16 = 2;
+pub(super) const TAG_POLICY: u16 = 3;
+pub(super) const TAG_COVERAGE_DIGEST: u16 = 4;
+pub(super) const TAG_KEY_FINGERPRINT: u16 = 5;
+pub(super) const TAG_VERSION: u16 = 6;
+pub(super) const TAG_PRODUCT_ID: u16 = 7;
+pub(super) const TAG_SIGNING_TIME: u16 = 8;
+pub(super) const TAG_ORIGINAL_SHOFF: u16 = 9;
+pub(super) const TAG_SECTIONS: u16 = 10;
+pub(super) const TAG_HEADERS: u16 = 11;
+pub(super) const TAG_HASH: u16 = 12;
+pub(super) const TAG_SEGMENTS: u16 = 13;
+
+// 읽기 위치를 관리하는 간단한 커서
+pub(super) struct Reader<'a> {
+    pub data: &'a [u8],
+    pub pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        let end = self.pos.checked_add(len).filter(|&e| e <= self.data.len()).ok_or("truncated")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    pub fn u16(&mut self) -> Result<u16, &'static str> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, &'static str> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, &'static str> {
+        u64_value(self.take(8)?)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+}
+
+pub(super) fn u64_value(value: &[u8]) -> Result<u64, &'static str> {
+    let bytes: [u8; 8] = value.try_into().map_err(|_| "bad integer length")?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+pub(super) fn digest32(value: &[u8]) -> Result<[u8; 32], &'static str> {
+    value.try_into().map_err(|_| "bad digest length")
+}
+
+pub(super) fn digest(value: &[u8], hash: HashAlgorithm) -> Result<Digest, &'static str> {
+    if value.len() != hash.output_len() {
+        return Err("bad digest length");
+    }
+    Ok(Digest::new(value))
+}
+
+pub(super) fn text(value: &[u8]) -> Result<&str, &'static str> {
+    str::from_utf8(value).map_err(|_| "invalid UTF-8")
+}
+
+// 서명 대상 블록의 TLV 레코드 (태그, 값)
+pub(super) struct Records<'a> {
+    reader: Reader<'a>,
+}
+
+impl<'a> Records<'a> {
+    pub fn new(data: &'a [u8]) -> Records<'a> {
+        Records { reader: Reader { data, pos: 0 } }
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Result<(u16, &'a [u8]), &'static str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.is_empty() {
+            return None;
+        }
+        let mut record = || {
+            let tag = self.reader.u16()?;
+            let len = self.reader.u32()? as usize;
+            Ok((tag, self.reader.take(len)?))
+        };
+        Some(record())
+    }
+}
+
+// .signature 섹션 내용을 나눈 것
+pub struct SignatureView<'a> {
+    pub version: u16,
+    pub signed_bytes: &'a [u8],
+    pub signature: &'a [u8],
+}
+
+impl<'a> SignatureView<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<SignatureView<'a>, &'static str> {
+        let mut reader = Reader { data, pos: 0 };
+        if reader.take(4)? != MAGIC {
+            return Err("bad magic");
+        }
+        let version = reader.u16()?;
+        if version != FORMAT_VERSION {
+            return Err("unsupported format version");
+        }
+        reader.u16()?;
+
+        let signed_len = reader.u32()? as usize;
+        let signed_bytes = reader.take(signed_len)?;
+        let sig_len = reader.u32()? as usize;
+        let signature = reader.take(sig_len)?;
+        if !reader.is_empty() {
+            return Err("trailing data");
+        }
+        Ok(SignatureView { version, signed_bytes, signature })
+    }
+}
+
+// 검증에 필요한 서명 속성 (나머지 레코드는 alloc 쪽 SignedAttributes 가 읽는다)
+pub struct AttributesView<'a> {
+    pub algorithm: &'a str,
+    pub policy_hash: [u8; 32],
+    pub policy: &'a str,
+    pub hash: HashAlgorithm,
+    pub coverage_digest: Digest,
+    pub key_fingerprint: [u8; 32],
+}
+
+impl<'a> AttributesView<'a> {
+    pub fn parse(signed_bytes: &'a [u8]) -> Result<AttributesView<'a>, &'static str> {
+        let mut algorithm = None;
+        let mut policy_hash = None;
+        let mut policy = None;
+        let mut coverage_digest = None;
+        let mut key_fingerprint = None;
+        let mut hash = HashAlgorithm::Sha256;
+        for record in Records::new(signed_bytes) {
+            let (tag, value) = record?;
+            match tag {
+                TAG_ALGORITHM => algorithm = Some(text(value)?),
+                TAG_POLICY_HASH => policy_hash = Some(digest32(value)?),
+                TAG_POLICY => policy = Some(text(value)?),
+                TAG_COVERAGE_DIGEST => coverage_digest = Some(value),
+                TAG_KEY_FINGERPRINT => key_fingerprint = Some(digest32(value)?),
+                TAG_HASH => hash = HashAlgorithm::from_name(text(value)?).ok_or("unknown hash algorithm")?,
+                _ => {},
+            }
+        }
+        Ok(AttributesView {
+            algorithm: algorithm.ok_or("missing algorithm")?,
+            policy_hash: policy_hash.ok_or("missing policy hash")?,
+            policy: policy.ok_or("missing policy")?,
+            hash,
+            coverage_digest: digest(coverage_digest.ok_or("missing coverage digest")?, hash)?,
+            key_fingerprint: key_fingerprint.ok_or("missing key fingerprint")?,
+        })
+    }
+}
+
+// 서명 범위를 정하는 규칙 (alloc 쪽 Policy 와 정책 텍스트를 그 자리에서 읽는 PolicyView 가 구현한다)
+//
+// 섹션 이름은 파일의 바이트 그대로 받는다. 규칙은 이를 UTF-8 로 (잘못된 부분은 U+FFFD 로) 읽는다.
+pub trait Rules {
+    fn matches_name(&self, name: &[u8]) -> bool;
+    fn matches_segment(&self, p_flags: u32) -> bool;
+    fn includes_headers(&self) -> bool;
+    fn includes_relocations(&self) -> bool;
+    fn includes_segments(&self) -> bool;
+    // 반드시 있어야 하는 섹션의 수와, index 번째 섹션의 이름이 name 인지
+    fn required_count(&self) -> usize;
+    fn is_required(&self, index: usize, name: &[u8]) -> bool;
+}
+
+// 서명에 기록된 정규화된 정책 텍스트 (Policy::canonical 의 출력) 를 복사 없이 읽는다
+pub struct PolicyView<'a> {
+    sections: &'a str,
+    patterns: &'a str,
+    // 정책의 각 segment_flags 값 f 에 대해 비트 f 를 켠다
+    flag_sets: u8,
+    include_headers: bool,
+    include_relocations: bool,
+    include_segments: bool,
+}
+
+impl<'a> PolicyView<'a> {
+    pub fn parse(text: &'a str) -> Result<PolicyView<'a>, &'static str> {
+        const NOT_CANONICAL: &str = "policy is not in canonical form";
+        let mut lines = text.split_terminator('\n');
+        let mut array = |key: &str| {
+            let line = lines.next().ok_or(NOT_CANONICAL)?;
+            let inner = line.strip_prefix(key).and_then(|rest| rest.strip_prefix(" = [")).and_then(|rest| rest.strip_suffix(']'));
+            inner.filter(|inner| items_valid(inner)).ok_or(NOT_CANONICAL)
+        };
+        let sections = array("sections")?;
+        let patterns = array("patterns")?;
+        let flags = array("segment_flags")?;
+
+        let mut flag_sets = 0u8;
+        for item in Items::new(flags) {
+            let mut set = 0;
+            for c in item.chars() {
+                set |= match c {
+                    'R' => PF_R,
+                    'W' => PF_W,
+                    'X' => PF_X,
+                    _ => return Err(NOT_CANONICAL),
+                };
+            }
+            flag_sets |= 1 << set;
+        }
+
+        let mut flag = |key: &str, optional: bool| match lines.clone().next().and_then(|line| line.strip_prefix(key)) {
+            Some(" = true") => {
+                lines.next();
+                Ok(true)
+            },
+            Some(" = false") if !optional => {
+                lines.next();
+                Ok(false)
+            },
+            _ if optional => Ok(false),
+            _ => Err(NOT_CANONICAL),
+        };
+        let include_headers = flag("include_headers", false)?;
+        let include_relocations = flag("include_relocations", false)?;
+        let include_segments = flag("include_segments", true)?;
+        if lines.next().is_some() {
+            return Err(NOT_CANONICAL);
+        }
+        Ok(PolicyView { sections, patterns, flag_sets, include_headers, include_relocations, include_segments })
+    }
+}
+
+impl Rules for PolicyView<'_> {
+    fn matches_name(&self, name: &[u8]) -> bool {
+        Items::new(self.sections).any(|item| unescape(item).eq(lossy(name)))
+            || Items::new(self.patterns).any(|item| glob_match_chars(unescape(item), lossy(name)))
+    }
+
+    fn matches_segment(&self, p_flags: u32) -> bool {
+        (1..8u32).any(|set| self.flag_sets & (1 << set) != 0 && set & !p_flags == 0)
+    }
+
+    fn includes_headers(&self) -> bool {
+        self.include_headers
+    }
+
+    fn includes_relocations(&self) -> bool {
+        self.include_relocations
+    }
+
+    fn includes_segments(&self) -> bool {
+        self.include_segments
+    }
+
+    fn required_count(&self) -> usize {
+        Items::new(self.sections).count()
+    }
+
+    fn is_required(&self, index: usize, name: &[u8]) -> bool {
+        Items::new(self.sections).nth(index).is_some_and(|item| unescape(item).eq(lossy(name)))
+    }
+}
+
+// 정책 배열 안의 따옴표로 감싼 항목들 (이스케이프는 풀지 않은 채로)
+struct Items<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Items<'a> {
+    fn new(inner: &'a str) -> Items<'a> {
+        Items { rest: inner }
+    }
+}
+
+impl<'a> Iterator for Items<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest.strip_prefix(", ").unwrap_or(self.rest).strip_prefix('"')?;
+        let mut escaped = false;
+        for (i, c) in rest.char_indices() {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => {
+                    self.rest = &rest[i + 1..];
+                    return Some(&rest[..i]);
+                },
+                _ => escaped = false,
+            }
+        }
+        None
+    }
+}
+
+// canonical 이 쓰는 모양인지 ("a", "b" 처럼 쉼표와 공백 하나로 구분하고 \\ 와 \" 만 이스케이프)
+fn items_valid(inner: &str) -> bool {
+    let mut items = Items::new(inner);
+    let mut first = true;
+    loop {
+        let before = items.rest;
+        if before.is_empty() {
+            return true;
+        }
+        if first == before.starts_with(", ") {
+            return false;
+        }
+        first = false;
+        match items.next() {
+            Some(item) if escapes_valid(item) => {},
+            _ => return false,
+        }
+    }
+}
+
+fn escapes_valid(item: &str) -> bool {
+    let mut chars = item.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' && !matches!(chars.next(), Some('\\' | '"')) {
+            return false;
+        }
+    }
+    true
+}
+
+// 이스케이프를 푼 문자들
+#[derive(Clone)]
+struct Unescape<'a>(str::Chars<'a>);
+
+impl Iterator for Unescape<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        match self.0.next()? {
+            '\\' => self.0.next(),
+            c => Some(c),
+        }
+    }
+}
+
+fn unescape(item: &str) -> Unescape<'_> {
+    Unescape(item.chars())
+}
+
+// String::from_utf8_lossy 와 같은 문자들 (잘못된 바이트 묶음마다 U+FFFD 하나)
+fn lossy(name: &[u8]) -> impl Iterator<Item = char> + Clone + '_ {
+    name.utf8_chunks().flat_map(|chunk| chunk.valid().chars().chain((!chunk.invalid().is_empty()).then_some('\u{FFFD}')))
+}
+
+// `*` 와 `?` 만 지원하는 glob 매칭
+pub fn glob_match_chars<P, N>(pattern: P, name: N) -> bool
+where
+    P: Iterator<Item = char> + Clone,
+    N: Iterator<Item = char> + Clone,
+{
+    let (mut p, mut n) = (pattern, name);
+    // 마지막 `*` 뒤의 패턴과, 그 `*` 가 지금까지 삼킨 이름의 끝
+    let mut star: Option<(P, N)> = None;
+
+    loop {
+        let mut next_n = n.clone();
+        let Some(c) = next_n.next() else {
+            break;
+        };
+        let mut next_p = p.clone();
+        match next_p.next() {
+            Some(pc) if pc == '?' || pc == c => {
+                p = next_p;
+                n = next_n;
+            },
+            Some('*') => {
+                star = Some((next_p.clone(), n.clone()));
+                p = next_p;
+            },
+            _ => match &mut star {
+                Some((star_p, star_n)) => {
+                    star_n.next();
+                    p = star_p.clone();
+                    n = star_n.clone();
+                },
+                None => return false,
+            },
+        }
+    }
+    p.all(|c| c == '*')
+}
+
+// 섹션이 서명 범위에 들어가는지
+//
+// .signature 와 섹션 이름 테이블은 서명 과정에서 새로 쓰이므로 항상 제외한다.
+// 이름 테이블이 바뀌어도 포함된 섹션의 이름은 다이제스트에 들어가므로 변조가 드러난다.
+pub fn section_covered<S, R>(image: &Image<S>, rules: &R, index: usize, section: &SectionHeader) -> Result<bool, ImageError>
+where
+    S: Source + ?Sized,
+    R: Rules + ?Sized,
+{
+    let name = image.section_name(section);
+    if index == 0 || index == image.header.e_shstrndx as usize || name == SIGNATURE_SECTION_NAME.as_bytes() {
+        return Ok(false);
+    }
+    if rules.matches_name(name) || (rules.includes_relocations() && matches!(section.sh_type, SHT_REL | SHT_RELA | SHT_RELR)) {
+        return Ok(true);
+    }
+    for segment_index in 0..image.header.e_phnum as usize {
+        let segment = image.segment(segment_index)?;
+        if segment.p_type == PT_LOAD && rules.matches_segment(segment.p_flags) && section.in_segment(&segment) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// include_segments 일 때 파일 범위 전체가 서명 범위에 들어가는 세그먼트
+// (섹션 사이의 패딩처럼 어느 섹션에도 속하지 않지만 실행되는 바이트를 포함하기 위함)
+pub fn segment_covered<R: Rules + ?Sized>(rules: &R, segment: &ProgramHeader) -> bool {
+    rules.includes_segments() && segment.p_type == PT_LOAD && segment.p_filesz > 0 && rules.matches_segment(segment.p_flags)
+}
+
+// 커널이 세그먼트를 매핑하는 페이지 단위 (아키텍처에 따라 다르므로 가장 큰 64 KiB 로 본다)
+const MAX_PAGE_SIZE: u64 = 0x10000;
+
+// 세그먼트에서 해시하는 파일 범위 [start, end)
+//
+// 세그먼트는 페이지 단위로 매핑되므로 p_filesz 뒤의 같은 페이지 바이트도 실행될 수 있다.
+// 그래서 페이지 경계까지 넓히되 서명 전 파일 끝 (file_end) 은 넘지 않는다. ELF 헤더에는 서명 시
+// 바뀌는 e_shoff / e_shnum 이 있으므로 헤더 뒤부터 해시한다 (헤더는 include_headers 로 따로 포함된다).
+pub fn segment_range(segment: &ProgramHeader, file_end: u64) -> Option<(u64, u64)> {
+    let end = segment.p_offset.checked_add(segment.p_filesz).filter(|&end| end <= file_end)?;
+    let start = (segment.p_offset & !(MAX_PAGE_SIZE - 1)).max(EHDR_SIZE as u64);
+    let end = end.checked_next_multiple_of(MAX_PAGE_SIZE).unwrap_or(u64::MAX).min(file_end);
+    Some((start.min(end), end))
+}
+
+// 서명 전 파일의 길이 (서명된 파일이면 signtool 이 덧붙인 .signature 내용이 시작하는 위치)
+pub fn unsigned_length<S: Source + ?Sized>(image: &Image<S>) -> Result<u64, ImageError> {
+    let size = image.source.size();
+    Ok(match image.find_section(SIGNATURE_SECTION_NAME.as_bytes())? {
+        Some((_, section)) => section.sh_offset.min(size),
+        None => size,
+    })
+}
+
+// 다시 계산한 서명 범위
+pub struct Coverage {
+    pub digest: Digest,
+    pub sections: usize,
+    pub segments: usize,
+    // 정책이 요구하는 섹션 중 파일에 없는 것이 있는지
+    pub missing: bool,
+}
+
+// 계산하는 대로 알려 주는 섹션과 세그먼트 다이제스트
+pub enum Covered<'a> {
+    Section { index: usize, name: &'a [u8], header: &'a SectionHeader, digest: Digest },
+    Segment { index: usize, header: &'a ProgramHeader, digest: Digest },
+}
+
+// 한 번에 digester 에 넘기는 섹션/세그먼트 수 (스택에 두는 배열의 크기)
+const BATCH: usize = 16;
+
+// 서명 범위 다이제스트 계산
+//
+// "signtool-coverage-v1\0" 뒤에, 헤더를 포함하는 경우 e_shoff / e_shnum 을 0 으로 둔 ELF 헤더와
+// 프로그램 헤더 테이블을, 이어서 각 섹션의 이름, 타입, 플래그, 주소, 오프셋, 크기와 내용 해시를 순서대로 누적한다.
+// 세그먼트를 포함하는 경우 그 뒤에 세그먼트 수와 각 세그먼트의 플래그, 위치, 크기와 내용 해시를 누적한다.
+// file_end 는 서명 전 파일의 길이다.
+pub fn coverage<S, R>(
+    image: &Image<S>,
+    rules: &R,
+    hash: HashAlgorithm,
+    file_end: u64,
+    digester: &dyn DigestMany,
+    observe: &mut dyn FnMut(Covered),
+) -> Result<Coverage, ImageError>
+where
+    S: Source + ?Sized,
+    R: Rules + ?Sized,
+{
+    let mut hasher = hash.hasher();
+    hasher.update(b"signtool-coverage-v1\0");
+
+    if rules.includes_headers() {
+        let mut ehdr = [0u8; EHDR_SIZE];
+        read(image.source, 0, &mut ehdr)?;
+        ehdr[E_SHOFF_OFFSET..E_SHOFF_OFFSET + 8].fill(0);
+        ehdr[E_SHNUM_OFFSET..E_SHNUM_OFFSET + 2].fill(0);
+        hasher.update(&ehdr);
+        let (start, end) = image.header.program_headers(image.source.size())?;
+        update_range(&mut hasher, image.source, start, end)?;
+    }
+
+    let mut batch: [Option<(usize, SectionHeader)>; BATCH] = [const { None }; BATCH];
+    let mut pending = 0;
+    let mut sections = 0;
+    for index in 0..image.header.e_shnum as usize {
+        let section = image.section(index)?;
+        if !section_covered(image, rules, index, &section)? {
+            continue;
+        }
+        batch[pending] = Some((index, section));
+        pending += 1;
+        sections += 1;
+        if pending == BATCH {
+            hash_sections(image, hash, &batch[..pending], digester, &mut hasher, observe)?;
+            pending = 0;
+        }
+    }
+    hash_sections(image, hash, &batch[..pending], digester, &mut hasher, observe)?;
+
+    // 세그먼트를 포함하지 않는 정책의 다이제스트는 세그먼트가 추가되기 전과 같다
+    let mut segments = 0;
+    if rules.includes_segments() {
+        for index in 0..image.header.e_phnum as usize {
+            let segment = image.segment(index)?;
+            if segment_covered(rules, &segment) {
+                segment_range(&segment, file_end).ok_or(ImageError::Malformed("segment out of bounds"))?;
+                segments += 1;
+            }
+        }
+        hasher.update(b"signtool-segments\0");
+        hasher.update(&(segments as u64).to_le_bytes());
+
+        let mut batch: [Option<(usize, ProgramHeader)>; BATCH] = [const { None }; BATCH];
+        let mut pending = 0;
+        for index in 0..image.header.e_phnum as usize {
+            let segment = image.segment(index)?;
+            if !segment_covered(rules, &segment) {
+                continue;
+            }
+            batch[pending] = Some((index, segment));
+            pending += 1;
+            if pending == BATCH {
+                hash_segments(image, hash, file_end, &batch[..pending], digester, &mut hasher, observe)?;
+                pending = 0;
+            }
+        }
+        hash_segments(image, hash, file_end, &batch[..pending], digester, &mut hasher, observe)?;
+    }
+
+    // 요구 섹션이 모두 있는지 (이름이 같은 섹션이 하나라도 있으면 된다)
+    let mut missing = false;
+    for required in 0..rules.required_count() {
+        let mut found = false;
+        for index in 0..image.header.e_shnum as usize {
+            if rules.is_required(required, image.section_name(&image.section(index)?)) {
+                found = true;
+                break;
+            }
+        }
+        missing |= !found;
+    }
+
+    Ok(Coverage { digest: hasher.finalize(), sections, segments, missing })
+}
+
+fn read<S: Source + ?Sized>(source: &S, offset: u64, buf: &mut [u8]) -> Result<(), ImageError> {
+    match source.read_at(offset, buf) {
+        true => Ok(()),
+        false => Err(ImageError::Read),
+    }
+}
+
+// [start, end) 를 고정 크기 버퍼로 나누어 hasher 에 넣는다
+fn update_range<S: Source + ?Sized>(hasher: &mut Hasher, source: &S, start: u64, end: u64) -> Result<(), ImageError> {
+    let mut buf = [0u8; 4096];
+    let mut offset = start;
+    while offset < end {
+        let len = (end - offset).min(buf.len() as u64) as usize;
+        read(source, offset, &mut buf[..len])?;
+        hasher.update(&buf[..len]);
+        offset += len as u64;
+    }
+    Ok(())
+}
+
+// ranges 의 다이제스트를 digester 로 계산 (읽기에 실패하면 Read)
+fn digest_ranges<S: Source + ?Sized>(
+    source: &S,
+    hash: HashAlgorithm,
+    ranges: &[(u64, u64)],
+    digester: &dyn DigestMany,
+    out: &mut [Digest],
+) -> Result<(), ImageError> {
+    let mut sizes = [0u64; BATCH];
+    for (size, (start, end)) in sizes.iter_mut().zip(ranges) {
+        *size = end - start;
+    }
+    let failed = AtomicBool::new(false);
+    let digest = |index: usize| {
+        let (start, end) = ranges[index];
+        hash.digest_range(source, start, end).unwrap_or_else(|| {
+            failed.store(true, Ordering::Relaxed);
+            Digest::EMPTY
+        })
+    };
+    digester.digest_many(&sizes[..ranges.len()], &digest, out);
+    match failed.load(Ordering::Relaxed) {
+        true => Err(ImageError::Read),
+        false => Ok(()),
+    }
+}
+
+fn hash_sections<S: Source + ?Sized>(
+    image: &Image<S>,
+    hash: HashAlgorithm,
+    batch: &[Option<(usize, SectionHeader)>],
+    digester: &dyn DigestMany,
+    hasher: &mut Hasher,
+    observe: &mut dyn FnMut(Covered),
+) -> Result<(), ImageError> {
+    let mut ranges = [(0u64, 0u64); BATCH];
+    for (range, (_, section)) in ranges.iter_mut().zip(batch.iter().flatten()) {
+        *range = section.file_range();
+    }
+    let mut digests = [Digest::EMPTY; BATCH];
+    digest_ranges(image.source, hash, &ranges[..batch.len()], digester, &mut digests)?;
+
+    for ((index, section), digest) in batch.iter().flatten().zip(digests) {
+        let name = image.section_name(section);
+        for chunk in name.utf8_chunks() {
+            hasher.update(chunk.valid().as_bytes());
+            if !chunk.invalid().is_empty() {
+                hasher.update("\u{FFFD}".as_bytes());
+            }
+        }
+        hasher.update(&[0u8]);
+        hasher.update(&section.sh_type.to_le_bytes());
+        hasher.update(&section.sh_flags.to_le_bytes());
+        hasher.update(&section.sh_addr.to_le_bytes());
+        hasher.update(&section.sh_offset.to_le_bytes());
+        hasher.update(&section.sh_size.to_le_bytes());
+        hasher.update(&digest);
+        observe(Covered::Section { index: *index, name, header: section, digest });
+    }
+    Ok(())
+}
+
+fn hash_segments<S: Source + ?Sized>(
+    image: &Image<S>,
+    hash: HashAlgorithm,
+    file_end: u64,
+    batch: &[Option<(usize, ProgramHeader)>],
+    digester: &dyn DigestMany,
+    hasher: &mut Hasher,
+    observe: &mut dyn FnMut(Covered),
+) -> Result<(), ImageError> {
+    let mut ranges = [(0u64, 0u64); BATCH];
+    for (range, (_, segment)) in ranges.iter_mut().zip(batch.iter().flatten()) {
+        *range = segment_range(segment, file_end).ok_or(ImageError::Malformed("segment out of bounds"))?;
+    }
+    let mut digests = [Digest::EMPTY; BATCH];
+    digest_ranges(image.source, hash, &ranges[..batch.len()], digester, &mut digests)?;
+
+    for ((index, segment), digest) in batch.iter().flatten().zip(digests) {
+        hasher.update(&segment.p_flags.to_le_bytes());
+        hasher.update(&segment.p_offset.to_le_bytes());
+        hasher.update(&segment.p_vaddr.to_le_bytes());
+        hasher.update(&segment.p_filesz.to_le_bytes());
+        hasher.update(&digest);
+        observe(Covered::Segment { index: *index, header: segment, digest });
+    }
+    Ok(())
+}
+
+// 서명 자체를 받아들이지 않는 이유
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    Format(&'static str),
+    AlgorithmNotAllowed,
+    UnsupportedKey,
+    DifferentKey,
+    SignatureMismatch,
+    PolicyMismatch,
+}
+
+impl Rejection {
+    pub fn reason(self) -> &'static str {
+        match self {
+            Rejection::Format(detail) => detail,
+            Rejection::AlgorithmNotAllowed => "algorithm is not allowed",
+            Rejection::UnsupportedKey => "public key cannot be used with the signature algorithm",
+            Rejection::DifferentKey => "signed with a different key",
+            Rejection::SignatureMismatch => "signature does not match",
+            Rejection::PolicyMismatch => "signed policy does not match its hash",
+        }
+    }
+}
+
+// 서명이 key 로 만들어졌고 허용된 알고리즘인지 확인한 뒤 서명 속성과 정책을 돌려준다
+pub fn authenticate<'s, A: AsRef<str>>(
+    signature: &'s [u8],
+    key: &dyn VerifyingKey,
+    allowed_algorithms: &[A],
+) -> Result<(AttributesView<'s>, PolicyView<'s>), Rejection> {
+    let view = SignatureView::parse(signature).map_err(Rejection::Format)?;
+    let attributes = AttributesView::parse(view.signed_bytes).map_err(Rejection::Format)?;
+
+    // 허용되지 않은 알고리즘으로 서명된 경우 (다운그레이드 방지)
+    if !allowed_algorithms.iter().any(|allowed| allowed.as_ref() == attributes.algorithm) {
+        return Err(Rejection::AlgorithmNotAllowed);
+    }
+    // 공개 키로 서명 대상 블록 검증
+    if !key.supports(attributes.algorithm) {
+        return Err(Rejection::UnsupportedKey);
+    }
+    if !ct_eq(&key.fingerprint(), &attributes.key_fingerprint) {
+        return Err(Rejection::DifferentKey);
+    }
+    if !key.verify(attributes.algorithm, view.signed_bytes, view.signature) {
+        return Err(Rejection::SignatureMismatch);
+    }
+    // 정책 텍스트는 서명 당시의 정규화된 형태이므로 그대로 해시한다
+    let policy = match PolicyView::parse(attributes.policy) {
+        Ok(policy) if ct_eq(&HashAlgorithm::Sha256.digest(attributes.policy.as_bytes()), &attributes.policy_hash) => policy,
+        _ => return Err(Rejection::PolicyMismatch),
+    };
+    Ok((attributes, policy))
+}
+
+pub enum Status {
+    NotSigned,
+    NotOk(&'static str),
+    // 서명이 유효하고 서명 범위가 그대로이면 intact
+    Verified { intact: bool },
+}
+
+// 메모리에 있는 이미지 검증 (부트로더처럼 할당자가 없는 곳에서 쓰는 진입점)
+pub fn verify_image<A: AsRef<str>>(data: &[u8], key: &dyn VerifyingKey, allowed_algorithms: &[A]) -> Result<Status, ImageError> {
+    let image = Image::from_bytes(data)?;
+    let Some((_, section)) = image.find_section(SIGNATURE_SECTION_NAME.as_bytes())? else {
+        return Ok(Status::NotSigned);
+    };
+    let (start, end) = image::range(data.len() as u64, section.sh_offset, section.sh_size)
+        .ok_or(ImageError::Malformed("section out of bounds"))?;
+    check(&image, &data[start as usize..end as usize], key, allowed_algorithms, &Sequential, &mut |_| {})
+}
+
+// 서명 섹션 내용 (signature) 으로 image 를 검증
+pub fn check<S: Source + ?Sized, A: AsRef<str>>(
+    image: &Image<S>,
+    signature: &[u8],
+    key: &dyn VerifyingKey,
+    allowed_algorithms: &[A],
+    digester: &dyn DigestMany,
+    observe: &mut dyn FnMut(Covered),
+) -> Result<Status, ImageError> {
+    let (attributes, policy) = match authenticate(signature, key, allowed_algorithms) {
+        Ok(authenticated) => authenticated,
+        Err(rejection) => return Ok(Status::NotOk(rejection.reason())),
+    };
+    let coverage = match coverage(image, &policy, attributes.hash, unsigned_length(image)?, digester, observe) {
+        Ok(coverage) => coverage,
+        Err(ImageError::Malformed(detail)) => return Ok(Status::NotOk(detail)),
+        Err(e) => return Err(e),
+    };
+    // 아무것도 포함하지 않는 서명은 어떤 변경도 드러내지 못하므로 받아들이지 않는다
+    if coverage.sections == 0 && coverage.segments == 0 && !coverage.missing {
+        return Ok(Status::NotOk("no section or segment is covered by the policy"));
+    }
+    let intact = !coverage.missing && ct_eq(&coverage.digest, &attributes.coverage_digest);
+    Ok(Status::Verified { intact })
+}
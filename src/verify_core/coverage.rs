@@ -0,0 +1,128 @@
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::borrowed::{self, Covered, SIGNATURE_SECTION_NAME};
+use super::elf::{self, Elf, ImageError, ProgramHeader, Section, Source};
+use super::error::{Error, Result};
+use super::hash::{Digest, DigestMany, HashAlgorithm};
+use super::policy::Policy;
+use super::signature::{SectionRecord, SegmentRecord};
+
+// 어느 섹션과 세그먼트가 포함되는지와 다이제스트 계산 방법은 할당 없이 쓰는 borrowed 에 있다
+pub use super::borrowed::{segment_covered, segment_range};
+
+// 정책에 따라 서명 범위에 포함되는 섹션 목록 (정책이 요구하는 섹션이 없으면 에러)
+pub fn covered_sections<'e, S: Source + ?Sized>(elf: &'e Elf<S>, policy: &Policy) -> Result<Vec<&'e Section>> {
+    if let Some(name) = missing_sections(elf, policy).first() {
+        return Err(Error::MissingSection { name: name.to_string() });
+    }
+    Ok(matching_sections(elf, policy))
+}
+
+// 정책이 요구하지만 파일에 없는 섹션 이름
+pub fn missing_sections<'p, S: Source + ?Sized>(elf: &Elf<S>, policy: &'p Policy) -> Vec<&'p str> {
+    policy.sections.iter().filter(|name| elf.find_section(name).is_none()).map(|name| name.as_str()).collect()
+}
+
+// 정책에 맞는 섹션 목록 (요구 섹션이 빠졌는지는 보지 않는다)
+pub fn matching_sections<'e, S: Source + ?Sized>(elf: &'e Elf<S>, policy: &Policy) -> Vec<&'e Section> {
+    let image = elf.image();
+    elf.sections
+        .iter()
+        .filter(|s| borrowed::section_covered(&image, policy, s.index, &s.header()).unwrap_or(false))
+        .collect()
+}
+
+// 주어진 섹션 내용의 다이제스트 (섹션 순서는 계산 방법과 무관하다)
+pub fn digest_sections<'e>(
+    elf: &'e Elf,
+    sections: Vec<&'e Section>,
+    hash: HashAlgorithm,
+    digester: &dyn DigestMany,
+) -> Vec<(&'e Section, Digest)> {
+    let sizes: Vec<u64> = sections.iter().map(|section| elf.section_data(section).len() as u64).collect();
+    let mut digests = vec![Digest::EMPTY; sections.len()];
+    digester.digest_many(&sizes, &|index| hash.digest(elf.section_data(sections[index])), &mut digests);
+    sections.into_iter().zip(digests).collect()
+}
+
+// include_segments 일 때 파일 범위 전체가 서명 범위에 들어가는 세그먼트
+pub fn covered_segments<'e, S: Source + ?Sized>(elf: &'e Elf<S>, policy: &Policy) -> Vec<&'e ProgramHeader> {
+    elf.segments.iter().filter(|seg| segment_covered(policy, seg)).collect()
+}
+
+// 서명 전 파일의 길이 (서명된 파일이면 signtool 이 덧붙인 .signature 내용이 시작하는 위치)
+pub fn unsigned_length<S: Source + ?Sized>(elf: &Elf<S>) -> u64 {
+    match elf.find_section(SIGNATURE_SECTION_NAME) {
+        Some(section) => section.sh_offset.min(elf.size()),
+        None => elf.size(),
+    }
+}
+
+// 서명 범위에 포함된 각 섹션과 세그먼트의 다이제스트와, 이를 합친 서명 범위 다이제스트
+pub struct Digests<'e> {
+    pub sections: Vec<(&'e Section, Digest)>,
+    pub segments: Vec<(&'e ProgramHeader, Digest)>,
+    pub digest: Digest,
+}
+
+// 서명 범위 다이제스트 계산 (계산 방법은 borrowed::coverage, file_end 는 서명 전 파일의 길이)
+pub fn digests<'e, S: Source + ?Sized>(
+    elf: &'e Elf<S>,
+    policy: &Policy,
+    hash: HashAlgorithm,
+    file_end: u64,
+    digester: &dyn DigestMany,
+) -> Result<Digests<'e>> {
+    let mut sections = Vec::new();
+    let mut segments = Vec::new();
+    let coverage = borrowed::coverage(&elf.image(), policy, hash, file_end, digester, &mut |covered| match covered {
+        Covered::Section { index, digest, .. } => sections.push((&elf.sections[index], digest)),
+        Covered::Segment { index, digest, .. } => segments.push((&elf.segments[index], digest)),
+    })?;
+    Ok(Digests { sections, segments, digest: coverage.digest })
+}
+
+// 서명 시 바뀌는 e_shoff / e_shnum 을 0 으로 둔 ELF 헤더와 프로그램 헤더 테이블
+pub fn header_bytes<S: Source + ?Sized>(elf: &Elf<S>) -> Result<Vec<u8>> {
+    let (start, end) = elf.header.program_headers(elf.size())?;
+    let mut headers = vec![0u8; elf::EHDR_SIZE + (end - start) as usize];
+    let (ehdr, phdrs) = headers.split_at_mut(elf::EHDR_SIZE);
+    if !elf.data.read_at(0, ehdr) || !elf.data.read_at(start, phdrs) {
+        return Err(ImageError::Read.into());
+    }
+    headers[elf::E_SHOFF_OFFSET..elf::E_SHOFF_OFFSET + 8].fill(0);
+    headers[elf::E_SHNUM_OFFSET..elf::E_SHNUM_OFFSET + 2].fill(0);
+    Ok(headers)
+}
+
+// 서명에 기록할 섹션별 정보
+pub fn section_records(digests: &[(&Section, Digest)]) -> Vec<SectionRecord> {
+    digests
+        .iter()
+        .map(|(section, digest)| SectionRecord {
+            name: section.name.clone(),
+            sh_type: section.sh_type,
+            sh_flags: section.sh_flags,
+            sh_addr: section.sh_addr,
+            sh_offset: section.sh_offset,
+            sh_size: section.sh_size,
+            digest: *digest,
+        })
+        .collect()
+}
+
+// 서명에 기록할 세그먼트별 정보
+pub fn segment_records(digests: &[(&ProgramHeader, Digest)]) -> Vec<SegmentRecord> {
+    digests
+        .iter()
+        .map(|(segment, digest)| SegmentRecord {
+            p_flags: segment.p_flags,
+            p_offset: segment.p_offset,
+            p_vaddr: segment.p_vaddr,
+            p_filesz: segment.p_filesz,
+            digest: *digest,
+        })
+        .collect()
+}
@@ -0,0 +1,180 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::error::{Error, Result};
+
+// ELF64 (리틀 엔디언) 파서
+//
+// 헤더 형식과 범위 확인은 할당 없이 쓰는 image.rs 에 있고, 여기서는 모든 항목을 목록으로 모은다.
+// 내용은 메모리에 있는 &[u8] 이거나 파일처럼 필요한 부분만 읽는 Source 이다.
+pub use super::image::*;
+
+// Source 에서 통째로 읽어 들이는 섹션 (이름 테이블, .signature) 의 최대 크기
+pub const MAX_READ_SECTION: u64 = 16 << 20;
+
+#[derive(Clone, Debug)]
+pub struct Section {
+    pub index: usize,
+    pub name: String,
+    pub sh_name: u32,
+    pub sh_type: u32,
+    pub sh_flags: u64,
+    pub sh_addr: u64,
+    pub sh_offset: u64,
+    pub sh_size: u64,
+    pub sh_link: u32,
+}
+
+impl Section {
+    pub fn header(&self) -> SectionHeader {
+        SectionHeader {
+            sh_name: self.sh_name,
+            sh_type: self.sh_type,
+            sh_flags: self.sh_flags,
+            sh_addr: self.sh_addr,
+            sh_offset: self.sh_offset,
+            sh_size: self.sh_size,
+            sh_link: self.sh_link,
+        }
+    }
+}
+
+pub struct Elf<'a, S: ?Sized = [u8]> {
+    pub data: &'a S,
+    pub header: Header,
+    pub segments: Vec<ProgramHeader>,
+    pub sections: Vec<Section>,
+    // 섹션 이름 테이블 내용
+    names: Cow<'a, [u8]>,
+}
+
+impl<'a> Elf<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Elf<'a>> {
+        let header = Header::parse(data)?;
+        Elf::with_header(data, header)
+    }
+
+    // 헤더 필드를 파일 내용 대신 주어진 값으로 해석 (서명 전 원본을 복사 없이 다룰 때 사용)
+    pub(crate) fn with_header(data: &'a [u8], header: Header) -> Result<Elf<'a>> {
+        let names = match names_range(data, &header)? {
+            Some((start, end)) => &data[start as usize..end as usize],
+            None => &[],
+        };
+        Elf::build(data, header, Cow::Borrowed(names))
+    }
+
+    // 섹션의 파일 내용 (NOBITS 섹션은 빈 슬라이스)
+    pub fn section_data(&self, section: &Section) -> &'a [u8] {
+        let (start, end) = section.header().file_range();
+        match range(self.data.len() as u64, start, end - start) {
+            Some((start, end)) => &self.data[start as usize..end as usize],
+            None => &[],
+        }
+    }
+
+    // 프로그램 헤더 테이블의 원본 바이트
+    pub fn program_header_bytes(&self) -> &'a [u8] {
+        let size = self.header.e_phnum as u64 * PHDR_SIZE as u64;
+        match range(self.data.len() as u64, self.header.e_phoff, size) {
+            Some((start, end)) => &self.data[start as usize..end as usize],
+            None => &[],
+        }
+    }
+}
+
+impl<'a, S: Source + ?Sized> Elf<'a, S> {
+    // 헤더와 테이블만 읽어 해석 (섹션 내용은 필요할 때 읽는다)
+    pub fn read(source: &'a S) -> Result<Elf<'a, S>> {
+        let header = Header::read(source)?;
+        let names = match names_range(source, &header)? {
+            Some((start, end)) => read_range(source, start, end)?,
+            None => Vec::new(),
+        };
+        Elf::build(source, header, Cow::Owned(names))
+    }
+
+    fn build(data: &'a S, header: Header, names: Cow<'a, [u8]>) -> Result<Elf<'a, S>> {
+        let image = Image::new(data, header, &names)?;
+        let segments = (0..image.header.e_phnum as usize).map(|index| image.segment(index)).collect::<core::result::Result<_, _>>()?;
+        let mut sections = Vec::new();
+        for index in 0..image.header.e_shnum as usize {
+            let section = image.section(index)?;
+            sections.push(Section {
+                index,
+                name: String::from_utf8_lossy(image.section_name(&section)).into_owned(),
+                sh_name: section.sh_name,
+                sh_type: section.sh_type,
+                sh_flags: section.sh_flags,
+                sh_addr: section.sh_addr,
+                sh_offset: section.sh_offset,
+                sh_size: section.sh_size,
+                sh_link: section.sh_link,
+            });
+        }
+        let header = image.header;
+        Ok(Elf { data, header, segments, sections, names })
+    }
+
+    // 할당 없이 읽는 쪽 (borrowed) 에 넘길 이미지 (범위는 parse 에서 이미 확인했다)
+    pub fn image(&self) -> Image<'_, S> {
+        Image { source: self.data, header: self.header.clone(), names: &self.names }
+    }
+
+    // 파일 크기
+    pub fn size(&self) -> u64 {
+        self.data.size()
+    }
+
+    pub fn find_section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    // 섹션 내용 (메모리에 있으면 빌리고, 아니면 MAX_READ_SECTION 까지 읽는다)
+    pub fn read_section(&self, section: &Section) -> Result<Cow<'a, [u8]>> {
+        let (start, end) = section.header().file_range();
+        if let Some(bytes) = self.data.as_bytes() {
+            return Ok(Cow::Borrowed(bytes.get(start as usize..end as usize).unwrap_or(&[])));
+        }
+        read_range(self.data, start, end).map(Cow::Owned)
+    }
+
+    // 섹션이 주어진 세그먼트의 파일 범위 안에 있는지 확인
+    pub fn section_in_segment(section: &Section, segment: &ProgramHeader) -> bool {
+        section.header().in_segment(segment)
+    }
+
+    // PT_DYNAMIC 세그먼트의 (태그, 값) 목록 (DT_NULL 에서 끝난다)
+    pub fn dynamic_entries(&self) -> Vec<(i64, u64)> {
+        let mut entries = Vec::new();
+        for segment in self.segments.iter().filter(|s| s.p_type == PT_DYNAMIC) {
+            let Some((start, end)) = range(self.data.size(), segment.p_offset, segment.p_filesz) else {
+                continue;
+            };
+            let mut entry = [0u8; 16];
+            let mut off = start;
+            while off + 16 <= end && self.data.read_at(off, &mut entry) {
+                let tag = read_u64(&entry, 0) as i64;
+                if tag == DT_NULL {
+                    break;
+                }
+                entries.push((tag, read_u64(&entry, 8)));
+                off += 16;
+            }
+        }
+        entries
+    }
+}
+
+// source 의 [start, end) 를 읽는다 (MAX_READ_SECTION 보다 크면 에러)
+fn read_range<S: Source + ?Sized>(source: &S, start: u64, end: u64) -> Result<Vec<u8>> {
+    if end - start > MAX_READ_SECTION {
+        return Err(Error::MalformedElf { detail: "section too large to read".to_string() });
+    }
+    let mut buf = vec![0u8; (end - start) as usize];
+    if !source.read_at(start, &mut buf) {
+        return Err(ImageError::Read.into());
+    }
+    Ok(buf)
+}
@@ -0,0 +1,44 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use super::image::ImageError;
+
+// 검증 코어의 오류 (std 쪽에서는 같은 이름의 SigntoolError 변형으로 바뀐다)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    NotElf,
+    MalformedElf { detail: String },
+    UnsupportedClass,
+    SignatureFormat { detail: String },
+    Policy { line: usize, detail: String },
+    MissingSection { name: String },
+    NothingCovered,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotElf => write!(f, "Invalid ELF file"),
+            Error::MalformedElf { detail } => write!(f, "Malformed ELF file: {}", detail),
+            Error::UnsupportedClass => write!(f, "Unsupported ELF class (only little-endian ELF64 is supported)"),
+            Error::SignatureFormat { detail } => write!(f, "Malformed signature section: {}", detail),
+            Error::Policy { line, detail } => write!(f, "Invalid policy file: line {}: {}", line, detail),
+            Error::MissingSection { name } => write!(f, "Required section {} not found", name),
+            Error::NothingCovered => write!(f, "no section or segment is covered by the policy"),
+        }
+    }
+}
+
+impl From<ImageError> for Error {
+    fn from(e: ImageError) -> Error {
+        match e {
+            ImageError::NotElf => Error::NotElf,
+            ImageError::UnsupportedClass => Error::UnsupportedClass,
+            ImageError::Malformed(detail) => Error::MalformedElf { detail: detail.to_string() },
+            // 메모리 위의 이미지는 읽기에 실패하지 않으므로 범위를 벗어난 경우다
+            ImageError::Read => Error::MalformedElf { detail: "read out of bounds".to_string() },
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt;
+use core::ops::Deref;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+
+use super::blake3::Blake3;
+use super::image::Source;
+use super::sha3::Sha3_256;
+
+// 서명 범위 다이제스트에 쓰는 해시 알고리즘 (서명 속성에 기록된다)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 5] = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Blake3,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha384 => "sha384",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Sha3_256 => "sha3-256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<HashAlgorithm> {
+        HashAlgorithm::ALL.into_iter().find(|alg| alg.name() == name)
+    }
+
+    pub fn output_len(self) -> usize {
+        match self {
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+            _ => 32,
+        }
+    }
+
+    pub fn hasher(self) -> Hasher {
+        match self {
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Sha384 => Hasher::Sha384(Sha384::new()),
+            HashAlgorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            HashAlgorithm::Sha3_256 => Hasher::Sha3_256(Sha3_256::new()),
+            HashAlgorithm::Blake3 => Hasher::Blake3(Blake3::new()),
+        }
+    }
+
+    pub fn digest(self, data: &[u8]) -> Digest {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    // source 의 [start, end) 다이제스트 (메모리에 없으면 고정 크기 버퍼로 나누어 읽는다)
+    pub fn digest_range<S: Source + ?Sized>(self, source: &S, start: u64, end: u64) -> Option<Digest> {
+        if let Some(bytes) = source.as_bytes() {
+            return bytes.get(start as usize..end as usize).map(|data| self.digest(data));
+        }
+        let mut hasher = self.hasher();
+        let mut buf = [0u8; READ_CHUNK];
+        let mut offset = start;
+        while offset < end {
+            let len = (end - offset).min(READ_CHUNK as u64) as usize;
+            if !source.read_at(offset, &mut buf[..len]) {
+                return None;
+            }
+            hasher.update(&buf[..len]);
+            offset += len as u64;
+        }
+        Some(hasher.finalize())
+    }
+}
+
+// digest_range 가 한 번에 읽는 크기
+const READ_CHUNK: usize = 16 << 10;
+
+// 여러 내용의 다이제스트를 계산해 out 에 입력 순서대로 채운다 (std 쪽에서는 스레드로 나누어 계산한다)
+//
+// i 번째 내용은 sizes[i] 바이트이고 그 다이제스트는 digest(i) 로 계산한다.
+pub trait DigestMany {
+    fn digest_many(&self, sizes: &[u64], digest: &(dyn Fn(usize) -> Digest + Sync), out: &mut [Digest]);
+}
+
+// 한 스레드에서 차례로 계산
+pub struct Sequential;
+
+impl DigestMany for Sequential {
+    fn digest_many(&self, sizes: &[u64], digest: &(dyn Fn(usize) -> Digest + Sync), out: &mut [Digest]) {
+        for (index, slot) in out.iter_mut().enumerate().take(sizes.len()) {
+            *slot = digest(index);
+        }
+    }
+}
+
+// 해시 하나를 계산하는 동안만 쓰이므로 변형 크기 차이는 문제되지 않는다
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum Hasher {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Sha3_256(Sha3_256),
+    Blake3(Blake3),
+}
+
+impl Hasher {
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha384(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+            Hasher::Sha3_256(h) => h.update(data),
+            Hasher::Blake3(h) => h.update(data),
+        }
+    }
+
+    pub fn finalize(self) -> Digest {
+        match self {
+            Hasher::Sha256(h) => Digest::new(&h.finalize()),
+            Hasher::Sha384(h) => Digest::new(&h.finalize()),
+            Hasher::Sha512(h) => Digest::new(&h.finalize()),
+            Hasher::Sha3_256(h) => Digest::new(&h.finalize()),
+            Hasher::Blake3(h) => Digest::new(&h.finalize()),
+        }
+    }
+}
+
+// 최대 64 바이트의 다이제스트 값 (할당 없이 복사할 수 있도록 고정 크기 버퍼에 담는다)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Digest {
+    bytes: [u8; 64],
+    len: u8,
+}
+
+impl Digest {
+    // 아직 계산하지 않은 자리를 채우는 빈 값
+    pub const EMPTY: Digest = Digest { bytes: [0; 64], len: 0 };
+
+    // bytes 는 64 바이트 이하여야 한다
+    pub fn new(bytes: &[u8]) -> Digest {
+        let mut digest = Digest { bytes: [0; 64], len: bytes.len() as u8 };
+        digest.bytes[..bytes.len()].copy_from_slice(bytes);
+        digest
+    }
+}
+
+impl Deref for Digest {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+}
+
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.iter() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
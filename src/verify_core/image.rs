@@ -0,0 +1,337 @@
+// 할당 없이 읽는 ELF64 (리틀 엔디언) 이미지
+//
+// 헤더와 테이블을 한 항목씩 Source 에서 읽으므로 메모리에 올린 &[u8] 뿐 아니라
+// std 쪽의 pread 로 읽는 파일에도 그대로 쓸 수 있다. 항목을 모아 두는 alloc 쪽 파서는 elf.rs 에 있다.
+
+pub const EHDR_SIZE: usize = 64;
+pub const PHDR_SIZE: usize = 56;
+pub const SHDR_SIZE: usize = 64;
+
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+
+// 섹션 타입
+pub const SHT_PROGBITS: u32 = 1;
+pub const SHT_SYMTAB: u32 = 2;
+pub const SHT_RELA: u32 = 4;
+pub const SHT_NOBITS: u32 = 8;
+pub const SHT_REL: u32 = 9;
+pub const SHT_DYNSYM: u32 = 11;
+pub const SHT_RELR: u32 = 19;
+
+// 파일 타입
+pub const ET_EXEC: u16 = 2;
+
+// 세그먼트 타입과 플래그
+pub const PT_LOAD: u32 = 1;
+pub const PT_DYNAMIC: u32 = 2;
+pub const PT_GNU_STACK: u32 = 0x6474_e551;
+pub const PT_GNU_RELRO: u32 = 0x6474_e552;
+pub const PF_X: u32 = 1;
+pub const PF_W: u32 = 2;
+pub const PF_R: u32 = 4;
+
+// 동적 섹션 태그와 플래그
+pub const DT_NULL: i64 = 0;
+pub const DT_BIND_NOW: i64 = 24;
+pub const DT_FLAGS: i64 = 30;
+pub const DT_FLAGS_1: i64 = 0x6fff_fffb;
+pub const DF_BIND_NOW: u64 = 0x8;
+pub const DF_1_NOW: u64 = 0x1;
+
+// 서명 시 바뀌는 ELF 헤더 필드의 위치
+pub const E_SHOFF_OFFSET: usize = 0x28;
+pub const E_SHNUM_OFFSET: usize = 0x3C;
+
+// 파일 내용을 읽는 곳
+pub trait Source: Sync {
+    fn size(&self) -> u64;
+    // [offset, offset + buf.len()) 을 모두 읽었으면 true (std 쪽 구현은 실패 원인을 따로 기록한다)
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> bool;
+    // 내용 전체가 메모리에 있으면 복사 없이 빌려 준다
+    fn as_bytes(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
+impl Source for [u8] {
+    fn size(&self) -> u64 {
+        self.len() as u64
+    }
+
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> bool {
+        match range(self.size(), offset, buf.len() as u64) {
+            Some((start, end)) => {
+                buf.copy_from_slice(&self[start as usize..end as usize]);
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+// 헤더와 테이블이 잘못된 경우 (alloc 쪽에서는 같은 이름의 Error 변형으로 바뀐다)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageError {
+    NotElf,
+    UnsupportedClass,
+    Malformed(&'static str),
+    // Source 에서 읽지 못함
+    Read,
+}
+
+pub fn read_u16(data: &[u8], off: usize) -> u16 {
+    u16::from_le_bytes([data[off], data[off + 1]])
+}
+
+pub fn read_u32(data: &[u8], off: usize) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&data[off..off + 4]);
+    u32::from_le_bytes(buf)
+}
+
+pub fn read_u64(data: &[u8], off: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&data[off..off + 8]);
+    u64::from_le_bytes(buf)
+}
+
+// 크기 size 인 파일 안의 [offset, offset + len) 구간
+pub fn range(size: u64, offset: u64, len: u64) -> Option<(u64, u64)> {
+    let end = offset.checked_add(len).filter(|&end| end <= size)?;
+    Some((offset, end))
+}
+
+#[derive(Clone, Debug)]
+pub struct Header {
+    pub e_type: u16,
+    pub e_phoff: u64,
+    pub e_shoff: u64,
+    pub e_phentsize: u16,
+    pub e_phnum: u16,
+    pub e_shentsize: u16,
+    pub e_shnum: u16,
+    pub e_shstrndx: u16,
+}
+
+impl Header {
+    // ELF 헤더 (EHDR_SIZE 바이트) 해석
+    pub fn parse(ehdr: &[u8]) -> Result<Header, ImageError> {
+        if ehdr.len() < EHDR_SIZE || &ehdr[..4] != b"\x7fELF" {
+            return Err(ImageError::NotElf);
+        }
+        if ehdr[4] != ELFCLASS64 || ehdr[5] != ELFDATA2LSB {
+            return Err(ImageError::UnsupportedClass);
+        }
+        Ok(Header {
+            e_type: read_u16(ehdr, 0x10),
+            e_phoff: read_u64(ehdr, 0x20),
+            e_shoff: read_u64(ehdr, E_SHOFF_OFFSET),
+            e_phentsize: read_u16(ehdr, 0x36),
+            e_phnum: read_u16(ehdr, 0x38),
+            e_shentsize: read_u16(ehdr, 0x3A),
+            e_shnum: read_u16(ehdr, E_SHNUM_OFFSET),
+            e_shstrndx: read_u16(ehdr, 0x3E),
+        })
+    }
+
+    pub fn read<S: Source + ?Sized>(source: &S) -> Result<Header, ImageError> {
+        if source.size() < EHDR_SIZE as u64 {
+            return Err(ImageError::NotElf);
+        }
+        let mut ehdr = [0u8; EHDR_SIZE];
+        if !source.read_at(0, &mut ehdr) {
+            return Err(ImageError::Read);
+        }
+        Header::parse(&ehdr)
+    }
+
+    // 프로그램 헤더 테이블의 파일 범위
+    pub fn program_headers(&self, size: u64) -> Result<(u64, u64), ImageError> {
+        if self.e_phnum == 0 {
+            return Ok((0, 0));
+        }
+        if self.e_phentsize as usize != PHDR_SIZE {
+            return Err(ImageError::Malformed("unexpected program header size"));
+        }
+        range(size, self.e_phoff, self.e_phnum as u64 * PHDR_SIZE as u64)
+            .ok_or(ImageError::Malformed("program headers out of bounds"))
+    }
+
+    // 섹션 헤더 테이블의 파일 범위
+    pub fn section_headers(&self, size: u64) -> Result<(u64, u64), ImageError> {
+        if self.e_shnum == 0 {
+            return Ok((0, 0));
+        }
+        if self.e_shentsize as usize != SHDR_SIZE {
+            return Err(ImageError::Malformed("unexpected section header size"));
+        }
+        range(size, self.e_shoff, self.e_shnum as u64 * SHDR_SIZE as u64)
+            .ok_or(ImageError::Malformed("section headers out of bounds"))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProgramHeader {
+    pub p_type: u32,
+    pub p_flags: u32,
+    pub p_offset: u64,
+    pub p_vaddr: u64,
+    pub p_filesz: u64,
+}
+
+impl ProgramHeader {
+    pub fn parse(entry: &[u8]) -> ProgramHeader {
+        ProgramHeader {
+            p_type: read_u32(entry, 0),
+            p_flags: read_u32(entry, 4),
+            p_offset: read_u64(entry, 8),
+            p_vaddr: read_u64(entry, 16),
+            p_filesz: read_u64(entry, 32),
+        }
+    }
+}
+
+// 이름을 풀지 않은 섹션 헤더
+#[derive(Clone, Debug)]
+pub struct SectionHeader {
+    pub sh_name: u32,
+    pub sh_type: u32,
+    pub sh_flags: u64,
+    pub sh_addr: u64,
+    pub sh_offset: u64,
+    pub sh_size: u64,
+    pub sh_link: u32,
+}
+
+impl SectionHeader {
+    pub fn parse(entry: &[u8]) -> SectionHeader {
+        SectionHeader {
+            sh_name: read_u32(entry, 0),
+            sh_type: read_u32(entry, 4),
+            sh_flags: read_u64(entry, 8),
+            sh_addr: read_u64(entry, 16),
+            sh_offset: read_u64(entry, 24),
+            sh_size: read_u64(entry, 32),
+            sh_link: read_u32(entry, 40),
+        }
+    }
+
+    // 섹션 내용의 파일 범위 (NOBITS 섹션은 빈 범위)
+    pub fn file_range(&self) -> (u64, u64) {
+        if self.sh_type == SHT_NOBITS {
+            return (0, 0);
+        }
+        (self.sh_offset, self.sh_offset.saturating_add(self.sh_size))
+    }
+
+    // 섹션이 주어진 세그먼트의 파일 범위 안에 있는지 확인
+    pub fn in_segment(&self, segment: &ProgramHeader) -> bool {
+        if self.sh_type == SHT_NOBITS || self.sh_size == 0 {
+            return false;
+        }
+        let end = self.sh_offset.saturating_add(self.sh_size);
+        self.sh_offset >= segment.p_offset && end <= segment.p_offset.saturating_add(segment.p_filesz)
+    }
+}
+
+// 섹션 이름 테이블 (names) 에서 sh_name 위치의 이름 (범위를 벗어나면 빈 이름)
+pub fn section_name(names: &[u8], sh_name: u32) -> &[u8] {
+    let start = sh_name as usize;
+    if start >= names.len() {
+        return &[];
+    }
+    let end = names[start..].iter().position(|&b| b == 0).map_or(names.len(), |p| start + p);
+    &names[start..end]
+}
+
+// 테이블 범위를 확인한 ELF 이미지 (섹션 이름 테이블 내용은 호출한 쪽이 준다)
+pub struct Image<'a, S: ?Sized> {
+    pub source: &'a S,
+    pub header: Header,
+    pub names: &'a [u8],
+}
+
+impl<'a> Image<'a, [u8]> {
+    // 메모리에 있는 이미지 (이름 테이블은 이미지 안을 빌린다)
+    pub fn from_bytes(data: &'a [u8]) -> Result<Image<'a, [u8]>, ImageError> {
+        let header = Header::parse(data)?;
+        let names = match names_range(data, &header)? {
+            Some((start, end)) => &data[start as usize..end as usize],
+            None => &[],
+        };
+        Image::new(data, header, names)
+    }
+}
+
+// 섹션 이름 테이블의 파일 범위 (섹션 헤더가 없으면 None)
+pub fn names_range<S: Source + ?Sized>(source: &S, header: &Header) -> Result<Option<(u64, u64)>, ImageError> {
+    header.program_headers(source.size())?;
+    let (start, _) = header.section_headers(source.size())?;
+    if header.e_shnum == 0 {
+        return Ok(None);
+    }
+    if header.e_shstrndx >= header.e_shnum {
+        return Err(ImageError::Malformed("section name table out of bounds"));
+    }
+    let mut entry = [0u8; SHDR_SIZE];
+    if !source.read_at(start + header.e_shstrndx as u64 * SHDR_SIZE as u64, &mut entry) {
+        return Err(ImageError::Read);
+    }
+    let strtab = SectionHeader::parse(&entry);
+    range(source.size(), strtab.sh_offset, strtab.sh_size)
+        .map(Some)
+        .ok_or(ImageError::Malformed("section name table out of bounds"))
+}
+
+impl<'a, S: Source + ?Sized> Image<'a, S> {
+    // 테이블과 모든 섹션 내용이 파일 안에 있는지 확인
+    pub fn new(source: &'a S, header: Header, names: &'a [u8]) -> Result<Image<'a, S>, ImageError> {
+        let size = source.size();
+        header.program_headers(size)?;
+        header.section_headers(size)?;
+        let image = Image { source, header, names };
+        for index in 0..image.header.e_shnum as usize {
+            let section = image.section(index)?;
+            if section.sh_type != SHT_NOBITS && range(size, section.sh_offset, section.sh_size).is_none() {
+                return Err(ImageError::Malformed("section out of bounds"));
+            }
+        }
+        Ok(image)
+    }
+
+    pub fn segment(&self, index: usize) -> Result<ProgramHeader, ImageError> {
+        let mut entry = [0u8; PHDR_SIZE];
+        if !self.source.read_at(self.header.e_phoff + (index * PHDR_SIZE) as u64, &mut entry) {
+            return Err(ImageError::Read);
+        }
+        Ok(ProgramHeader::parse(&entry))
+    }
+
+    pub fn section(&self, index: usize) -> Result<SectionHeader, ImageError> {
+        let mut entry = [0u8; SHDR_SIZE];
+        if !self.source.read_at(self.header.e_shoff + (index * SHDR_SIZE) as u64, &mut entry) {
+            return Err(ImageError::Read);
+        }
+        Ok(SectionHeader::parse(&entry))
+    }
+
+    pub fn section_name(&self, section: &SectionHeader) -> &'a [u8] {
+        section_name(self.names, section.sh_name)
+    }
+
+    // 이름이 name 인 첫 번째 섹션
+    pub fn find_section(&self, name: &[u8]) -> Result<Option<(usize, SectionHeader)>, ImageError> {
+        for index in 0..self.header.e_shnum as usize {
+            let section = self.section(index)?;
+            if self.section_name(&section) == name {
+                return Ok(Some((index, section)));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,22 @@
+use core::ptr;
+
+// 검증에 쓰는 공개 키 연산
+pub trait VerifyingKey {
+    // 공개 키 DER 의 SHA-256
+    fn fingerprint(&self) -> [u8; 32];
+    // 키가 해당 알고리즘에 쓰일 수 있는지
+    fn supports(&self, algorithm: &str) -> bool;
+    fn verify(&self, algorithm: &str, data: &[u8], signature: &[u8]) -> bool;
+}
+
+// 상수 시간 비교 (길이는 비밀이 아니므로 먼저 비교)
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    unsafe { ptr::read_volatile(&diff) == 0 }
+}
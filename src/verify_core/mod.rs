@@ -0,0 +1,116 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+// std 없이 (core + alloc 만으로) 동작하는 검증 코어
+//
+// ELF 파싱, .signature 형식 디코딩, 서명 범위 계산과 서명 검증 순서를 &[u8] 위에서 처리한다.
+// 파일, 스레드, OpenSSL 같은 std 쪽 기능은 쓰지 않으며, 공개 키 연산은 VerifyingKey 로 받아
+// 부트로더처럼 OpenSSL 이 없는 환경에서는 자체 구현을 넣을 수 있다.
+// 할당자도 없는 환경은 borrowed::verify_image 를 쓴다 (image, hash, key, borrowed 는 alloc 을 쓰지 않는다).
+// 이 디렉터리의 코드는 std:: 와 crate:: 경로를 쓰지 않는다 (make check-no-std 로 확인).
+
+mod blake3;
+pub mod borrowed;
+pub mod coverage;
+pub mod elf;
+mod error;
+pub mod hash;
+pub mod image;
+pub mod key;
+pub mod policy;
+mod sha3;
+pub mod signature;
+
+pub use borrowed::SIGNATURE_SECTION_NAME;
+pub use error::{Error, Result};
+pub use key::{ct_eq, VerifyingKey};
+
+use borrowed::Rejection;
+use elf::{Elf, ProgramHeader, Section, Source};
+use hash::{Digest, DigestMany};
+use policy::Policy;
+use signature::{SignatureSection, SignedAttributes};
+
+// 서명이 만족해야 하는 조건
+pub struct Requirements<'r> {
+    pub allowed_algorithms: &'r [String],
+    // 서명 정책이 적어도 이만큼 강해야 한다
+    pub policy: Option<&'r Policy>,
+}
+
+// 서명 자체가 유효할 때 돌려주는 내용 (서명 범위가 그대로인지는 intact)
+pub struct Verified<'e> {
+    pub attributes: SignedAttributes,
+    pub policy: Policy,
+    pub digests: Vec<(&'e Section, Digest)>,
+    pub segments: Vec<(&'e ProgramHeader, Digest)>,
+    // 정책이 요구하지만 파일에서 사라진 섹션 (하나라도 있으면 intact 가 아니다)
+    pub missing: Vec<String>,
+    pub intact: bool,
+}
+
+pub enum Check<'e> {
+    NotSigned,
+    // 디코딩된 경우 서명 속성도 함께 돌려준다
+    NotOk { attributes: Option<SignedAttributes>, reason: String },
+    Verified(Verified<'e>),
+}
+
+// 서명 섹션을 찾아 디코딩하고, 알고리즘, 키, 서명, 정책을 확인한 뒤 서명 범위를 다시 해시
+// (섹션별 해시는 digester 로 계산하며, 스레드가 없으면 hash::Sequential)
+pub fn check<'e, S: Source + ?Sized>(
+    elf: &'e Elf<S>,
+    key: &dyn VerifyingKey,
+    requirements: &Requirements,
+    digester: &dyn DigestMany,
+) -> Check<'e> {
+    let Some(section) = elf.find_section(SIGNATURE_SECTION_NAME) else {
+        return Check::NotSigned;
+    };
+    let contents = match elf.read_section(section) {
+        Ok(contents) => contents,
+        Err(e) => return Check::NotOk { attributes: None, reason: e.to_string() },
+    };
+    let signature = match SignatureSection::decode(&contents) {
+        Ok(signature) => signature,
+        Err(e) => return Check::NotOk { attributes: None, reason: e.to_string() },
+    };
+    let attributes = signature.attributes;
+    let not_ok = |attributes, reason: &str| Check::NotOk { attributes: Some(attributes), reason: reason.to_string() };
+
+    // 알고리즘, 키, 서명과 정책 해시 확인 (할당 없는 경로와 같은 순서와 조건)
+    if let Err(rejection) = borrowed::authenticate(&contents, key, requirements.allowed_algorithms) {
+        let reason = match rejection {
+            // 허용되지 않은 알고리즘으로 서명된 경우 (다운그레이드 방지)
+            Rejection::AlgorithmNotAllowed => format!("algorithm {} is not allowed", attributes.algorithm),
+            rejection => rejection.reason().to_string(),
+        };
+        return not_ok(attributes, &reason);
+    }
+
+    // 서명 당시 정책 복원 및 요구 정책과 비교
+    let policy = match Policy::parse(&attributes.policy) {
+        Ok(policy) if ct_eq(&policy.hash(), &attributes.policy_hash) => policy,
+        _ => return not_ok(attributes, Rejection::PolicyMismatch.reason()),
+    };
+    if let Some(required) = requirements.policy {
+        if !policy.is_at_least(required) {
+            return not_ok(attributes, "signed under a weaker policy than required");
+        }
+    }
+
+    // 서명 범위의 다이제스트 비교
+    // (요구 섹션이 사라져도 남은 섹션의 다이제스트는 돌려주어 어디가 바뀌었는지 보일 수 있게 한다)
+    let missing: Vec<String> = coverage::missing_sections(elf, &policy).into_iter().map(|name| name.to_string()).collect();
+    let digests = match coverage::digests(elf, &policy, attributes.hash, coverage::unsigned_length(elf), digester) {
+        Ok(digests) => digests,
+        Err(e) => return not_ok(attributes, &e.to_string()),
+    };
+    // 아무것도 포함하지 않는 서명은 어떤 변경도 드러내지 못하므로 받아들이지 않는다
+    if digests.sections.is_empty() && digests.segments.is_empty() && missing.is_empty() {
+        return not_ok(attributes, &Error::NothingCovered.to_string());
+    }
+    let intact = missing.is_empty() && ct_eq(&digests.digest, &attributes.coverage_digest);
+    Check::Verified(Verified { attributes, policy, digests: digests.sections, segments: digests.segments, missing, intact })
+}
@@ -0,0 +1,11 @@
+// 할당 없이 쓰는 검증 경로만 alloc 없이 컴파일해 보는 크레이트 루트 (make check-no-std)
+#![no_std]
+// 태그와 읽기 도구 중 alloc 쪽 signature.rs 만 쓰는 것이 있다
+#![allow(dead_code)]
+
+mod blake3;
+pub mod borrowed;
+pub mod hash;
+pub mod image;
+pub mod key;
+mod sha3;
@@ -0,0 +1,7 @@
+// verify_core 를 std 없이 단독으로 컴파일해 보는 크레이트 루트 (make check-no-std)
+#![no_std]
+
+extern crate alloc;
+
+#[path = "mod.rs"]
+pub mod verify_core;
@@ -0,0 +1,379 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use sha2::{Sha256, Digest};
+
+use super::borrowed::{glob_match_chars, Rules};
+use super::elf::{PF_R, PF_W, PF_X};
+use super::error::{Error, Result};
+
+// 서명 범위(coverage) 정책
+//
+// 정책 파일은 TOML 의 부분 집합으로 작성한다.
+//
+//   sections = [".text", ".init"]       # 반드시 존재하고 서명되어야 하는 섹션
+//   patterns = [".rela.*", ".plt*"]     # 이름이 일치하는 섹션도 서명 범위에 포함
+//   segment_flags = ["X"]               # 해당 플래그를 모두 가진 PT_LOAD 안의 섹션 포함
+//   include_headers = true              # ELF 헤더와 프로그램 헤더 테이블 포함
+//   include_relocations = true          # 모든 재배치 섹션 포함
+//   include_segments = true             # segment_flags 에 일치하는 PT_LOAD 의 파일 범위 전체 포함
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Policy {
+    pub sections: Vec<String>,
+    pub patterns: Vec<String>,
+    pub segment_flags: Vec<u32>,
+    pub include_headers: bool,
+    pub include_relocations: bool,
+    pub include_segments: bool,
+}
+
+impl Default for Policy {
+    // 과제 명세의 기본 동작 (실행 가능한 세그먼트 안의 섹션) 에 더해, 진입점과 세그먼트 배치가 담긴
+    // 헤더와 실행 가능한 세그먼트 중 어느 섹션에도 속하지 않는 바이트 (패딩 등) 도 서명
+    fn default() -> Self {
+        Policy {
+            sections: Vec::new(),
+            patterns: Vec::new(),
+            segment_flags: vec![PF_X],
+            include_headers: true,
+            include_relocations: false,
+            include_segments: true,
+        }
+    }
+}
+
+fn invalid(line: usize, msg: &str) -> Error {
+    Error::Policy { line, detail: msg.to_string() }
+}
+
+impl Policy {
+    pub fn parse(text: &str) -> Result<Policy> {
+        let mut policy = Policy {
+            sections: Vec::new(),
+            patterns: Vec::new(),
+            segment_flags: Vec::new(),
+            include_headers: false,
+            include_relocations: false,
+            include_segments: false,
+        };
+
+        let lines: Vec<&str> = text.lines().collect();
+        let mut i = 0;
+        while i < lines.len() {
+            let line_no = i + 1;
+            let mut line = strip_comment(lines[i]).trim().to_string();
+            i += 1;
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('[') {
+                return Err(invalid(line_no, "tables are not supported"));
+            }
+
+            let eq = line.find('=').ok_or_else(|| invalid(line_no, "expected `key = value`"))?;
+            let key = line[..eq].trim().to_string();
+
+            // 여러 줄에 걸친 배열은 닫는 괄호까지 이어 붙인다
+            if line[eq + 1..].trim_start().starts_with('[') {
+                while !line.ends_with(']') {
+                    if i >= lines.len() {
+                        return Err(invalid(line_no, "unterminated array"));
+                    }
+                    line.push(' ');
+                    line.push_str(strip_comment(lines[i]).trim());
+                    i += 1;
+                }
+            }
+            let value = line[eq + 1..].trim();
+
+            match key.as_str() {
+                "sections" => policy.sections = parse_string_array(value, line_no)?,
+                "patterns" => policy.patterns = parse_string_array(value, line_no)?,
+                "segment_flags" => {
+                    policy.segment_flags = parse_string_array(value, line_no)?
+                        .iter()
+                        .map(|f| parse_flags(f).ok_or_else(|| invalid(line_no, &format!("invalid segment flags `{}`", f))))
+                        .collect::<Result<Vec<u32>>>()?;
+                },
+                "include_headers" => policy.include_headers = parse_bool(value, line_no)?,
+                "include_relocations" => policy.include_relocations = parse_bool(value, line_no)?,
+                "include_segments" => policy.include_segments = parse_bool(value, line_no)?,
+                _ => return Err(invalid(line_no, &format!("unknown key `{}`", key))),
+            }
+        }
+
+        // 아무것도 고르지 않는 정책으로 서명하면 어떤 변경도 검증에서 드러나지 않는다
+        if policy.sections.is_empty() && policy.patterns.is_empty() && policy.segment_flags.is_empty() {
+            return Err(invalid(lines.len().max(1), "policy selects nothing to sign (set sections, patterns or segment_flags)"));
+        }
+
+        policy.normalize();
+        Ok(policy)
+    }
+
+    fn normalize(&mut self) {
+        self.sections.sort();
+        self.sections.dedup();
+        self.patterns.sort();
+        self.patterns.dedup();
+        self.segment_flags.sort();
+        self.segment_flags.dedup();
+    }
+
+    // 정규화된 정책 텍스트 (.signature 에 기록되며, 다시 parse 할 수 있다)
+    pub fn canonical(&self) -> String {
+        let quote = |items: &[String]| -> String {
+            items.iter().map(|s| format!("\"{}\"", escape(s))).collect::<Vec<_>>().join(", ")
+        };
+        let flags: Vec<String> = self.segment_flags.iter().map(|&f| flags_to_string(f)).collect();
+        let mut text = format!(
+            "sections = [{}]\npatterns = [{}]\nsegment_flags = [{}]\ninclude_headers = {}\ninclude_relocations = {}\n",
+            quote(&self.sections),
+            quote(&self.patterns),
+            quote(&flags),
+            self.include_headers,
+            self.include_relocations,
+        );
+        // 나중에 추가된 키는 켜진 경우에만 써서 이전에 서명된 정책의 해시가 바뀌지 않게 한다
+        if self.include_segments {
+            text.push_str("include_segments = true\n");
+        }
+        text
+    }
+
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.canonical().as_bytes()).into()
+    }
+
+    // self(서명 당시 정책)가 required 이상으로 강한지 확인
+    pub fn is_at_least(&self, required: &Policy) -> bool {
+        if required.include_headers && !self.include_headers {
+            return false;
+        }
+        if required.include_relocations && !self.include_relocations {
+            return false;
+        }
+        if required.include_segments && !self.include_segments {
+            return false;
+        }
+        if !required.sections.iter().all(|s| self.sections.contains(s)) {
+            return false;
+        }
+        // 패턴은 동일한 패턴이 있거나, 와일드카드 없는 이름을 기존 패턴이 포함하는 경우만 인정
+        let pattern_covered = |p: &String| {
+            self.patterns.iter().any(|q| q == p || (!has_wildcard(p) && glob_match(q, p)))
+        };
+        if !required.patterns.iter().all(pattern_covered) {
+            return false;
+        }
+        // 요구 플래그를 모두 가진 세그먼트는, 그 부분 집합 플래그 조건에도 일치한다
+        required
+            .segment_flags
+            .iter()
+            .all(|&r| self.segment_flags.iter().any(|&s| s & !r == 0))
+    }
+
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.sections.iter().any(|s| s == name) || self.patterns.iter().any(|p| glob_match(p, name))
+    }
+}
+
+// 서명 범위 계산 (borrowed::coverage) 이 읽는 규칙
+impl Rules for Policy {
+    fn matches_name(&self, name: &[u8]) -> bool {
+        Policy::matches_name(self, &String::from_utf8_lossy(name))
+    }
+
+    fn matches_segment(&self, p_flags: u32) -> bool {
+        self.segment_flags.iter().any(|&f| f & !p_flags == 0)
+    }
+
+    fn includes_headers(&self) -> bool {
+        self.include_headers
+    }
+
+    fn includes_relocations(&self) -> bool {
+        self.include_relocations
+    }
+
+    fn includes_segments(&self) -> bool {
+        self.include_segments
+    }
+
+    fn required_count(&self) -> usize {
+        self.sections.len()
+    }
+
+    fn is_required(&self, index: usize, name: &[u8]) -> bool {
+        self.sections.get(index).is_some_and(|section| *section == String::from_utf8_lossy(name))
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' if in_string && !escaped => {
+                escaped = true;
+                continue;
+            },
+            '"' if !escaped => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {},
+        }
+        escaped = false;
+    }
+    line
+}
+
+fn parse_bool(value: &str, line_no: usize) -> Result<bool> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(invalid(line_no, "expected `true` or `false`")),
+    }
+}
+
+fn parse_string_array(value: &str, line_no: usize) -> Result<Vec<String>> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or_else(|| invalid(line_no, "expected an array of strings"))?;
+
+    let mut items = Vec::new();
+    let mut chars = inner.chars().peekable();
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        match chars.next() {
+            None => break,
+            Some('"') => {},
+            Some(_) => return Err(invalid(line_no, "expected a quoted string")),
+        }
+
+        let mut item = String::new();
+        loop {
+            match chars.next() {
+                None => return Err(invalid(line_no, "unterminated string")),
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('"') => item.push('"'),
+                    Some('\\') => item.push('\\'),
+                    _ => return Err(invalid(line_no, "unsupported escape sequence")),
+                },
+                Some(c) => item.push(c),
+            }
+        }
+        items.push(item);
+
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        match chars.next() {
+            None => break,
+            Some(',') => {},
+            Some(_) => return Err(invalid(line_no, "expected `,` between array items")),
+        }
+    }
+    Ok(items)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn parse_flags(s: &str) -> Option<u32> {
+    let mut flags = 0;
+    for c in s.chars() {
+        flags |= match c.to_ascii_uppercase() {
+            'R' => PF_R,
+            'W' => PF_W,
+            'X' => PF_X,
+            _ => return None,
+        };
+    }
+    if flags == 0 { None } else { Some(flags) }
+}
+
+fn flags_to_string(flags: u32) -> String {
+    let mut s = String::new();
+    if flags & PF_R != 0 {
+        s.push('R');
+    }
+    if flags & PF_W != 0 {
+        s.push('W');
+    }
+    if flags & PF_X != 0 {
+        s.push('X');
+    }
+    s
+}
+
+fn has_wildcard(s: &str) -> bool {
+    s.contains('*') || s.contains('?')
+}
+
+// `*` 와 `?` 만 지원하는 glob 매칭
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    glob_match_chars(pattern.chars(), name.chars())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(text: &str) -> Policy {
+        Policy::parse(text).unwrap()
+    }
+
+    #[test]
+    fn glob() {
+        assert!(glob_match(".text", ".text"));
+        assert!(!glob_match(".text", ".text2"));
+        assert!(glob_match(".rela.*", ".rela.dyn"));
+        assert!(glob_match(".rela.*", ".rela."));
+        assert!(!glob_match(".rela.*", ".rel.dyn"));
+        assert!(glob_match(".plt*", ".plt"));
+        assert!(glob_match(".plt*", ".plt.got"));
+        assert!(glob_match(".?ata", ".data"));
+        assert!(!glob_match(".?ata", ".ata"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*.*t", ".rela.text"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        // 여러 바이트 문자도 한 글자로 센다
+        assert!(glob_match(".?", ".한"));
+    }
+
+    #[test]
+    fn stronger_policy_is_at_least() {
+        let signed = policy(
+            "sections = [\".text\", \".init\"]\npatterns = [\".rela.*\"]\nsegment_flags = [\"X\"]\n\
+             include_headers = true\ninclude_relocations = true\ninclude_segments = true\n",
+        );
+        assert!(signed.is_at_least(&signed));
+        assert!(signed.is_at_least(&policy("sections = [\".text\"]")));
+        // 와일드카드 없는 이름은 그것을 포함하는 패턴으로 충분하다
+        assert!(signed.is_at_least(&policy("patterns = [\".rela.dyn\"]")));
+        // RX 세그먼트를 요구하면 X 세그먼트를 모두 서명한 정책이 더 넓다
+        assert!(signed.is_at_least(&policy("segment_flags = [\"RX\"]")));
+        assert!(signed.is_at_least(&Policy::default()));
+    }
+
+    #[test]
+    fn weaker_policy_is_not_at_least() {
+        let signed = policy("sections = [\".text\"]\npatterns = [\".rela.*\"]\nsegment_flags = [\"RX\"]\n");
+        assert!(!signed.is_at_least(&policy("sections = [\".init\"]")));
+        // 다른 와일드카드 패턴은 포함 관계를 판단하지 않는다
+        assert!(!signed.is_at_least(&policy("patterns = [\".rela.d*\"]")));
+        assert!(!signed.is_at_least(&policy("patterns = [\".plt\"]")));
+        assert!(!signed.is_at_least(&policy("segment_flags = [\"X\"]")));
+        assert!(!signed.is_at_least(&policy("sections = [\".text\"]\ninclude_headers = true")));
+        assert!(!signed.is_at_least(&policy("sections = [\".text\"]\ninclude_relocations = true")));
+        assert!(!signed.is_at_least(&policy("sections = [\".text\"]\ninclude_segments = true")));
+        assert!(!signed.is_at_least(&Policy::default()));
+    }
+}
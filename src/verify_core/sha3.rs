@@ -0,0 +1,148 @@
+// SHA3-256 (FIPS 202)
+//
+// Keccak-f[1600] 순열 위의 스펀지 구성으로, 블록 크기(rate)는 136 바이트이고
+// 도메인 구분 패딩은 0x06 ... 0x80 이다.
+
+const RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001, 0x0000_0000_0000_8082, 0x8000_0000_0000_808a, 0x8000_0000_8000_8000,
+    0x0000_0000_0000_808b, 0x0000_0000_8000_0001, 0x8000_0000_8000_8081, 0x8000_0000_0000_8009,
+    0x0000_0000_0000_008a, 0x0000_0000_0000_0088, 0x0000_0000_8000_8009, 0x0000_0000_8000_000a,
+    0x0000_0000_8000_808b, 0x8000_0000_0000_008b, 0x8000_0000_0000_8089, 0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002, 0x8000_0000_0000_0080, 0x0000_0000_0000_800a, 0x8000_0000_8000_000a,
+    0x8000_0000_8000_8081, 0x8000_0000_0000_8080, 0x0000_0000_8000_0001, 0x8000_0000_8000_8008,
+];
+
+// rho 단계의 회전량과 pi 단계의 위치 (lane 1 부터 순서대로 따라간다)
+const ROTATIONS: [u32; 24] = [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+const PI_LANES: [usize; 24] = [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+
+fn keccak_f(state: &mut [u64; 25]) {
+    for round_constant in ROUND_CONSTANTS {
+        // theta
+        let mut column = [0u64; 5];
+        for x in 0..5 {
+            column[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = column[(x + 4) % 5] ^ column[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho + pi
+        let mut current = state[1];
+        for (&lane, &rotation) in PI_LANES.iter().zip(ROTATIONS.iter()) {
+            let next = state[lane];
+            state[lane] = current.rotate_left(rotation);
+            current = next;
+        }
+
+        // chi
+        for y in 0..5 {
+            let row = [state[5 * y], state[5 * y + 1], state[5 * y + 2], state[5 * y + 3], state[5 * y + 4]];
+            for x in 0..5 {
+                state[5 * y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // iota
+        state[0] ^= round_constant;
+    }
+}
+
+#[derive(Clone)]
+pub struct Sha3_256 {
+    state: [u64; 25],
+    buffer: [u8; RATE],
+    buffered: usize,
+}
+
+impl Default for Sha3_256 {
+    fn default() -> Self {
+        Sha3_256::new()
+    }
+}
+
+impl Sha3_256 {
+    pub fn new() -> Sha3_256 {
+        Sha3_256 { state: [0; 25], buffer: [0; RATE], buffered: 0 }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let n = (RATE - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + n].copy_from_slice(&data[..n]);
+            self.buffered += n;
+            data = &data[n..];
+            if self.buffered == RATE {
+                self.absorb_buffer();
+            }
+        }
+    }
+
+    pub fn finalize(mut self) -> [u8; 32] {
+        self.buffer[self.buffered..].fill(0);
+        self.buffer[self.buffered] ^= 0x06;
+        self.buffer[RATE - 1] ^= 0x80;
+        self.absorb_buffer();
+
+        let mut out = [0u8; 32];
+        for (chunk, lane) in out.chunks_exact_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&lane.to_le_bytes());
+        }
+        out
+    }
+
+    fn absorb_buffer(&mut self) {
+        for (lane, chunk) in self.state.iter_mut().zip(self.buffer.chunks_exact(8)) {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(chunk);
+            *lane ^= u64::from_le_bytes(bytes);
+        }
+        keccak_f(&mut self.state);
+        self.buffered = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // FIPS 202 예제 값 (0xa3 반복 입력은 블록 크기 136 바이트 직전, 정확히, 넘는 경우)
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"),
+        (b"abc", "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+        (&[0xa3; 135], "d51927265ca4bf0cc8b4453387700918c03f8894e395ad437d4573f3be4d2c34"),
+        (&[0xa3; 136], "0adf6bfb359ae40019b67d8c49c361574b70242a6b752de6f9e0d426ca177f7a"),
+        (&[0xa3; 200], "79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787"),
+    ];
+
+    #[test]
+    fn known_answers() {
+        for (input, expected) in VECTORS {
+            let mut hasher = Sha3_256::new();
+            hasher.update(input);
+            assert_eq!(hex(&hasher.finalize()), *expected, "{} bytes", input.len());
+        }
+    }
+
+    #[test]
+    fn incremental_updates() {
+        for (input, expected) in VECTORS {
+            for piece in [1, 7, 135, 136] {
+                let mut hasher = Sha3_256::new();
+                for chunk in input.chunks(piece) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hex(&hasher.finalize()), *expected, "{} bytes in {}-byte pieces", input.len(), piece);
+            }
+        }
+    }
+}
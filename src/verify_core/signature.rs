@@ -0,0 +1,344 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::borrowed::{
+    self, Reader, Records, SignatureView, TAG_ALGORITHM, TAG_COVERAGE_DIGEST, TAG_HASH, TAG_HEADERS, TAG_KEY_FINGERPRINT,
+    TAG_ORIGINAL_SHOFF, TAG_POLICY, TAG_POLICY_HASH, TAG_PRODUCT_ID, TAG_SECTIONS, TAG_SEGMENTS, TAG_SIGNING_TIME, TAG_VERSION,
+};
+use super::error::{Error, Result};
+use super::hash::{Digest, HashAlgorithm};
+
+// .signature 섹션의 형식은 borrowed.rs 에 있고, 여기서는 모든 레코드를 소유한 값으로 읽고 쓴다
+pub use super::borrowed::{FORMAT_VERSION, MAGIC};
+
+// 서명 당시 포함된 섹션 하나 (verify --explain 에서 변조 위치를 찾는 데 사용)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SectionRecord {
+    pub name: String,
+    pub sh_type: u32,
+    pub sh_flags: u64,
+    pub sh_addr: u64,
+    pub sh_offset: u64,
+    pub sh_size: u64,
+    pub digest: Digest,
+}
+
+// 서명 당시 파일 범위 전체가 포함된 세그먼트 하나 (정책의 include_segments)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SegmentRecord {
+    pub p_flags: u32,
+    pub p_offset: u64,
+    pub p_vaddr: u64,
+    pub p_filesz: u64,
+    pub digest: Digest,
+}
+
+// 서명으로 보호되는 속성
+#[derive(Clone, Debug)]
+pub struct SignedAttributes {
+    pub algorithm: String,
+    pub policy_hash: [u8; 32],
+    pub policy: String,
+    // 서명 범위 다이제스트의 해시 알고리즘 (sha256 이면 기록하지 않는다)
+    pub hash: HashAlgorithm,
+    pub coverage_digest: Digest,
+    pub key_fingerprint: [u8; 32],
+    // 롤백 방지용 버전과 제품 식별자 (sign --version 을 준 경우에만 기록)
+    pub version: Option<u64>,
+    pub product_id: Option<String>,
+    // 서명 시각 (유닉스 시간, 초)
+    pub signing_time: Option<u64>,
+    // 서명 전 e_shoff (unsign 으로 원본을 복원할 때 사용)
+    pub original_shoff: Option<u64>,
+    // 섹션별 다이제스트와 헤더 (e_shoff/e_shnum 을 0 으로 둔 ELF 헤더 + 프로그램 헤더)
+    pub sections: Vec<SectionRecord>,
+    pub headers: Option<Vec<u8>>,
+    pub segments: Vec<SegmentRecord>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SignatureSection {
+    pub version: u16,
+    pub attributes: SignedAttributes,
+    // 서명이 계산된 원본 바이트 (검증 시 그대로 사용)
+    pub signed_bytes: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+fn malformed(msg: &str) -> Error {
+    Error::SignatureFormat { detail: msg.to_string() }
+}
+
+fn put_record(out: &mut Vec<u8>, tag: u16, value: &[u8]) {
+    out.extend_from_slice(&tag.to_le_bytes());
+    out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    out.extend_from_slice(value);
+}
+
+fn digest(value: &[u8], hash: HashAlgorithm) -> Result<Digest> {
+    borrowed::digest(value, hash).map_err(malformed)
+}
+
+fn u64_value(value: &[u8]) -> Result<u64> {
+    borrowed::u64_value(value).map_err(malformed)
+}
+
+fn text(value: &[u8]) -> Result<String> {
+    borrowed::text(value).map(String::from).map_err(malformed)
+}
+
+fn encode_sections(sections: &[SectionRecord]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for section in sections {
+        out.extend_from_slice(&(section.name.len() as u16).to_le_bytes());
+        out.extend_from_slice(section.name.as_bytes());
+        out.extend_from_slice(&section.sh_type.to_le_bytes());
+        out.extend_from_slice(&section.sh_flags.to_le_bytes());
+        out.extend_from_slice(&section.sh_addr.to_le_bytes());
+        out.extend_from_slice(&section.sh_offset.to_le_bytes());
+        out.extend_from_slice(&section.sh_size.to_le_bytes());
+        out.extend_from_slice(&section.digest);
+    }
+    out
+}
+
+fn decode_sections(value: &[u8], hash: HashAlgorithm) -> Result<Vec<SectionRecord>> {
+    let mut reader = Reader { data: value, pos: 0 };
+    let mut sections = Vec::new();
+    while !reader.is_empty() {
+        let name_len = reader.u16().map_err(malformed)? as usize;
+        let name = text(reader.take(name_len).map_err(malformed)?)?;
+        sections.push(SectionRecord {
+            name,
+            sh_type: reader.u32().map_err(malformed)?,
+            sh_flags: reader.u64().map_err(malformed)?,
+            sh_addr: reader.u64().map_err(malformed)?,
+            sh_offset: reader.u64().map_err(malformed)?,
+            sh_size: reader.u64().map_err(malformed)?,
+            digest: digest(reader.take(hash.output_len()).map_err(malformed)?, hash)?,
+        });
+    }
+    Ok(sections)
+}
+
+fn encode_segments(segments: &[SegmentRecord]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for segment in segments {
+        out.extend_from_slice(&segment.p_flags.to_le_bytes());
+        out.extend_from_slice(&segment.p_offset.to_le_bytes());
+        out.extend_from_slice(&segment.p_vaddr.to_le_bytes());
+        out.extend_from_slice(&segment.p_filesz.to_le_bytes());
+        out.extend_from_slice(&segment.digest);
+    }
+    out
+}
+
+fn decode_segments(value: &[u8], hash: HashAlgorithm) -> Result<Vec<SegmentRecord>> {
+    let mut reader = Reader { data: value, pos: 0 };
+    let mut segments = Vec::new();
+    while !reader.is_empty() {
+        segments.push(SegmentRecord {
+            p_flags: reader.u32().map_err(malformed)?,
+            p_offset: reader.u64().map_err(malformed)?,
+            p_vaddr: reader.u64().map_err(malformed)?,
+            p_filesz: reader.u64().map_err(malformed)?,
+            digest: digest(reader.take(hash.output_len()).map_err(malformed)?, hash)?,
+        });
+    }
+    Ok(segments)
+}
+
+impl SignedAttributes {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        put_record(&mut out, TAG_ALGORITHM, self.algorithm.as_bytes());
+        put_record(&mut out, TAG_POLICY_HASH, &self.policy_hash);
+        put_record(&mut out, TAG_POLICY, self.policy.as_bytes());
+        put_record(&mut out, TAG_COVERAGE_DIGEST, &self.coverage_digest);
+        put_record(&mut out, TAG_KEY_FINGERPRINT, &self.key_fingerprint);
+        if let Some(version) = self.version {
+            put_record(&mut out, TAG_VERSION, &version.to_le_bytes());
+        }
+        if let Some(product_id) = &self.product_id {
+            put_record(&mut out, TAG_PRODUCT_ID, product_id.as_bytes());
+        }
+        if let Some(time) = self.signing_time {
+            put_record(&mut out, TAG_SIGNING_TIME, &time.to_le_bytes());
+        }
+        if let Some(shoff) = self.original_shoff {
+            put_record(&mut out, TAG_ORIGINAL_SHOFF, &shoff.to_le_bytes());
+        }
+        if !self.sections.is_empty() {
+            put_record(&mut out, TAG_SECTIONS, &encode_sections(&self.sections));
+        }
+        if let Some(headers) = &self.headers {
+            put_record(&mut out, TAG_HEADERS, headers);
+        }
+        if self.hash != HashAlgorithm::Sha256 {
+            put_record(&mut out, TAG_HASH, self.hash.name().as_bytes());
+        }
+        if !self.segments.is_empty() {
+            put_record(&mut out, TAG_SEGMENTS, &encode_segments(&self.segments));
+        }
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> Result<SignedAttributes> {
+        let mut algorithm = None;
+        let mut policy_hash = None;
+        let mut policy = None;
+        let mut coverage_digest = None;
+        let mut key_fingerprint = None;
+        let mut version = None;
+        let mut product_id = None;
+        let mut signing_time = None;
+        let mut original_shoff = None;
+        let mut sections = None;
+        let mut headers = None;
+        let mut hash = HashAlgorithm::Sha256;
+        let mut segments = None;
+
+        for record in Records::new(data) {
+            let (tag, value) = record.map_err(malformed)?;
+            let string = || text(value);
+            let fingerprint = || borrowed::digest32(value).map_err(malformed);
+            match tag {
+                TAG_ALGORITHM => algorithm = Some(string()?),
+                TAG_POLICY_HASH => policy_hash = Some(fingerprint()?),
+                TAG_POLICY => policy = Some(string()?),
+                TAG_COVERAGE_DIGEST => coverage_digest = Some(value),
+                TAG_KEY_FINGERPRINT => key_fingerprint = Some(fingerprint()?),
+                TAG_VERSION => version = Some(u64_value(value)?),
+                TAG_PRODUCT_ID => product_id = Some(string()?),
+                TAG_SIGNING_TIME => signing_time = Some(u64_value(value)?),
+                TAG_ORIGINAL_SHOFF => original_shoff = Some(u64_value(value)?),
+                TAG_SECTIONS => sections = Some(value),
+                TAG_HEADERS => headers = Some(value.to_vec()),
+                TAG_HASH => hash = HashAlgorithm::from_name(&string()?).ok_or_else(|| malformed("unknown hash algorithm"))?,
+                TAG_SEGMENTS => segments = Some(value),
+                // 알 수 없는 태그는 무시 (서명 대상이므로 변조는 검증에서 드러난다)
+                _ => {},
+            }
+        }
+
+        // 다이제스트 길이는 해시 알고리즘에 따라 다르므로 모든 레코드를 읽은 뒤 해석
+        let coverage_digest = coverage_digest.ok_or_else(|| malformed("missing coverage digest"))?;
+        Ok(SignedAttributes {
+            algorithm: algorithm.ok_or_else(|| malformed("missing algorithm"))?,
+            policy_hash: policy_hash.ok_or_else(|| malformed("missing policy hash"))?,
+            policy: policy.ok_or_else(|| malformed("missing policy"))?,
+            hash,
+            coverage_digest: digest(coverage_digest, hash)?,
+            key_fingerprint: key_fingerprint.ok_or_else(|| malformed("missing key fingerprint"))?,
+            version,
+            product_id,
+            signing_time,
+            original_shoff,
+            sections: sections.map_or(Ok(Vec::new()), |value| decode_sections(value, hash))?,
+            headers,
+            segments: segments.map_or(Ok(Vec::new()), |value| decode_segments(value, hash))?,
+        })
+    }
+}
+
+impl SignatureSection {
+    pub fn encode(signed_bytes: &[u8], signature: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out.extend_from_slice(&(signed_bytes.len() as u32).to_le_bytes());
+        out.extend_from_slice(signed_bytes);
+        out.extend_from_slice(&(signature.len() as u32).to_le_bytes());
+        out.extend_from_slice(signature);
+        out
+    }
+
+    pub fn decode(data: &[u8]) -> Result<SignatureSection> {
+        let view = SignatureView::parse(data).map_err(malformed)?;
+        let signed_bytes = view.signed_bytes.to_vec();
+        let signature = view.signature.to_vec();
+
+        Ok(SignatureSection {
+            version: view.version,
+            attributes: SignedAttributes::decode(&signed_bytes)?,
+            signed_bytes,
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(sections: Vec<SectionRecord>) -> SignedAttributes {
+        SignedAttributes {
+            algorithm: "ed25519".to_string(),
+            policy_hash: [1; 32],
+            policy: "segment_flags = [\"X\"]\n".to_string(),
+            hash: HashAlgorithm::Sha256,
+            coverage_digest: Digest::new(&[2; 32]),
+            key_fingerprint: [3; 32],
+            version: None,
+            product_id: None,
+            signing_time: None,
+            original_shoff: None,
+            sections,
+            headers: None,
+            segments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let section = SectionRecord {
+            name: ".text".to_string(),
+            sh_type: 1,
+            sh_flags: 6,
+            sh_addr: 0x1000,
+            sh_offset: 0x1000,
+            sh_size: 0x20,
+            digest: Digest::new(&[4; 32]),
+        };
+        let encoded = SignatureSection::encode(&attributes(vec![section.clone()]).encode(), &[5; 64]);
+        let decoded = SignatureSection::decode(&encoded).unwrap();
+        assert_eq!(decoded.version, FORMAT_VERSION);
+        assert_eq!(decoded.signature, [5; 64]);
+        assert_eq!(decoded.attributes.algorithm, "ed25519");
+        assert_eq!(decoded.attributes.sections, [section]);
+    }
+
+    // 어디서 잘려도 패닉 없이 형식 오류가 되어야 한다
+    #[test]
+    fn truncated_section_is_rejected() {
+        let section = SectionRecord {
+            name: ".text".to_string(),
+            sh_type: 1,
+            sh_flags: 6,
+            sh_addr: 0,
+            sh_offset: 0,
+            sh_size: 0,
+            digest: Digest::new(&[4; 32]),
+        };
+        let encoded = SignatureSection::encode(&attributes(vec![section]).encode(), &[5; 64]);
+        for len in 0..encoded.len() {
+            assert!(
+                matches!(SignatureSection::decode(&encoded[..len]), Err(Error::SignatureFormat { .. })),
+                "{} of {} bytes",
+                len,
+                encoded.len()
+            );
+        }
+    }
+
+    // 서명 대상 블록 길이는 맞지만 레코드가 잘린 경우 (필수 레코드만 있으므로 어느 위치든 실패)
+    #[test]
+    fn truncated_attributes_are_rejected() {
+        let signed = attributes(Vec::new()).encode();
+        for len in 0..signed.len() {
+            let encoded = SignatureSection::encode(&signed[..len], &[5; 64]);
+            assert!(matches!(SignatureSection::decode(&encoded), Err(Error::SignatureFormat { .. })), "{} bytes", len);
+        }
+    }
+}